@@ -4,6 +4,7 @@ use std::fs;
 use std::path::Path;
 
 use clap::CommandFactory;
+use clap_complete::Shell;
 
 include!("src/cli.rs");
 
@@ -19,4 +20,12 @@ fn main() {
     // Write to OUT_DIR so the binary can embed the man page at compile time
     let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
     fs::write(Path::new(&out_dir).join("ovc.1"), &buffer).expect("failed to write man page");
+
+    // Shell completions are embedded the same way
+    for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::Elvish] {
+        let mut script: Vec<u8> = Vec::new();
+        clap_complete::generate(shell, &mut Cli::command(), "ovc", &mut script);
+        fs::write(Path::new(&out_dir).join(format!("ovc.{shell}")), &script)
+            .expect("failed to write completion script");
+    }
 }
@@ -0,0 +1,237 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Content-addressed cache of downloaded client archives
+//!
+//! Verified archives are kept under `$XDG_CACHE_HOME/ovc/archives/`, stored
+//! by their sha256 digest and indexed by archive file name (e.g.
+//! `openshift-client-linux-4.19.0.tar.gz`). Reinstalling a pruned version
+//! then needs no download. The cache is bounded by size and evicts the
+//! least recently used archives first.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::get_cache_dir;
+use crate::checksum::sha256_file;
+
+/// Default size cap for the archive cache: 1 GiB (roughly a dozen releases)
+pub const ARCHIVE_CACHE_MAX_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// A single cached archive
+#[derive(Serialize, Deserialize, Clone)]
+struct ArchiveEntry {
+    /// Archive file name as published on the mirror
+    name: String,
+    /// Lowercase hex sha256 of the archive, also its blob file name
+    sha256: String,
+    /// Size of the archive in bytes
+    size: u64,
+    /// Unix timestamp (seconds since epoch) of the last install from this entry
+    last_used: u64,
+}
+
+/// On-disk index of cached archives, ordered from least to most recently used
+#[derive(Serialize, Deserialize, Default)]
+struct ArchiveIndex {
+    entries: Vec<ArchiveEntry>,
+}
+
+/// Size-capped archive store rooted at a directory
+pub struct ArchiveCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+fn current_unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl ArchiveCache {
+    /// Create an archive cache in `dir` holding at most `max_bytes`
+    #[must_use]
+    pub fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self { dir, max_bytes }
+    }
+
+    /// Open the default archive cache under the ovc cache directory
+    ///
+    /// # Errors
+    /// Returns error if the cache directory cannot be determined or created
+    pub fn open_default() -> Result<Self, Box<dyn Error>> {
+        Ok(Self::new(
+            get_cache_dir()?.join("archives"),
+            ARCHIVE_CACHE_MAX_BYTES,
+        ))
+    }
+
+    /// Directory holding the archives and their index
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Look up a cached archive by file name
+    ///
+    /// The archive is re-hashed before it is returned; entries whose blob is
+    /// missing or no longer matches its digest are dropped. A hit marks the
+    /// entry as most recently used.
+    ///
+    /// # Arguments
+    /// * `name` - Archive file name (e.g. `openshift-client-linux-4.19.0.tar.gz`)
+    ///
+    /// # Returns
+    /// `Some(path)` to the verified archive, `None` on a miss
+    ///
+    /// # Errors
+    /// Returns error if the index cannot be read or written
+    pub fn get(&self, name: &str) -> Result<Option<PathBuf>, Box<dyn Error>> {
+        let mut index = self.load_index()?;
+        let Some(pos) = index.entries.iter().position(|e| e.name == name) else {
+            return Ok(None);
+        };
+
+        let mut entry = index.entries.remove(pos);
+        let blob = self.blob_path(&entry.sha256);
+        let valid = sha256_file(&blob).is_ok_and(|digest| digest == entry.sha256);
+
+        if !valid {
+            if !index.entries.iter().any(|e| e.sha256 == entry.sha256) {
+                let _ = fs::remove_file(&blob);
+            }
+            self.save_index(&index)?;
+            return Ok(None);
+        }
+
+        entry.last_used = current_unix_timestamp();
+        index.entries.push(entry);
+        self.save_index(&index)?;
+        Ok(Some(blob))
+    }
+
+    /// Store a verified archive and evict old entries to stay under the cap
+    ///
+    /// The archive is copied into the cache, so `archive` may be removed
+    /// afterwards.
+    ///
+    /// # Arguments
+    /// * `name` - Archive file name to index the archive under
+    /// * `archive` - Path of the downloaded archive
+    /// * `sha256` - Expected lowercase hex digest of the archive
+    ///
+    /// # Returns
+    /// Path to the stored archive
+    ///
+    /// # Errors
+    /// Returns error if the archive does not match `sha256`, if it alone
+    /// exceeds the cap, or if the cache cannot be written
+    pub fn insert(
+        &self,
+        name: &str,
+        archive: &Path,
+        sha256: &str,
+    ) -> Result<PathBuf, Box<dyn Error>> {
+        let actual = sha256_file(archive)?;
+        if actual != sha256 {
+            return Err(
+                format!("sha256 mismatch for {name} (expected {sha256}, got {actual})").into(),
+            );
+        }
+        let size = fs::metadata(archive)?.len();
+        if size > self.max_bytes {
+            return Err(format!(
+                "{name} ({size} bytes) exceeds the archive cache limit of {} bytes",
+                self.max_bytes
+            )
+            .into());
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let blob = self.blob_path(sha256);
+        if !blob.exists() {
+            // Copy under a temporary name so a crash never leaves a truncated blob
+            let tmp = self.dir.join(format!("{sha256}.partial"));
+            fs::copy(archive, &tmp)?;
+            fs::rename(&tmp, &blob)?;
+        }
+
+        let mut index = self.load_index()?;
+        index.entries.retain(|e| e.name != name);
+        index.entries.push(ArchiveEntry {
+            name: name.to_string(),
+            sha256: sha256.to_string(),
+            size,
+            last_used: current_unix_timestamp(),
+        });
+        self.evict(&mut index);
+        self.save_index(&index)?;
+
+        Ok(blob)
+    }
+
+    /// Total size in bytes of all cached archives
+    ///
+    /// # Errors
+    /// Returns error if the index cannot be read
+    pub fn total_size(&self) -> Result<u64, Box<dyn Error>> {
+        Ok(unique_size(&self.load_index()?.entries))
+    }
+
+    /// Drop least recently used entries until the cache fits under the cap
+    ///
+    /// The most recently used entry is never evicted.
+    fn evict(&self, index: &mut ArchiveIndex) {
+        while index.entries.len() > 1 && unique_size(&index.entries) > self.max_bytes {
+            let evicted = index.entries.remove(0);
+            if !index.entries.iter().any(|e| e.sha256 == evicted.sha256) {
+                let _ = fs::remove_file(self.blob_path(&evicted.sha256));
+            }
+        }
+    }
+
+    fn blob_path(&self, sha256: &str) -> PathBuf {
+        self.dir.join(sha256)
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.dir.join("index.json")
+    }
+
+    fn load_index(&self) -> Result<ArchiveIndex, Box<dyn Error>> {
+        let path = self.index_path();
+        if !path.exists() {
+            return Ok(ArchiveIndex::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        // A corrupted index only costs re-downloads, so start over
+        Ok(serde_json::from_str(&content).unwrap_or_default())
+    }
+
+    fn save_index(&self, index: &ArchiveIndex) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.index_path(), serde_json::to_string_pretty(index)?)?;
+        Ok(())
+    }
+}
+
+/// Size of the distinct blobs referenced by `entries`
+fn unique_size(entries: &[ArchiveEntry]) -> u64 {
+    let mut seen: Vec<&str> = Vec::new();
+    entries
+        .iter()
+        .filter(|e| {
+            if seen.contains(&e.sha256.as_str()) {
+                false
+            } else {
+                seen.push(&e.sha256);
+                true
+            }
+        })
+        .map(|e| e.size)
+        .sum()
+}
@@ -3,7 +3,16 @@
 //!
 //! This module handles caching of version information with download URLs for all platforms
 //! to minimize API calls to the OpenShift mirror. The cache expires after 72 hours and is
-//! also updated when requested versions are not found.
+//! also updated when requested versions are not found. Refreshes are conditional requests
+//! using the listing's `ETag`/`Last-Modified`, so an unchanged listing costs a 304.
+//!
+//! Listings are fetched from the configured mirrors in order, failing over to
+//! the next mirror on errors. Each version's URLs point at the mirror that
+//! listed (or last served) it.
+//!
+//! Each listing has its own cache file: `versions.json` for `clients/ocp/`,
+//! which oc shares with the companion tools published next to it, and
+//! `versions-<tool>.json` for tools with their own tree.
 
 use std::collections::HashMap;
 use std::error::Error;
@@ -11,9 +20,14 @@ use std::fs;
 use std::path::PathBuf;
 use std::time::SystemTime;
 
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
 use serde::{Deserialize, Serialize};
 
-use crate::{Platform, compare_versions};
+use crate::index::{list_version_directories, parse_version_index};
+use crate::mirror::{configured_mirrors, local_path};
+use crate::tool::Tool;
+use crate::{OC_MIRROR_BASE, Platform, compare_versions, matches_version_pattern};
 
 /// Cache time-to-live: 72 hours in seconds
 const CACHE_TTL_SECS: u64 = 72 * 60 * 60;
@@ -38,6 +52,30 @@ pub struct VersionCache {
     versions: Vec<VersionInfo>,
     /// Unix timestamp (seconds since epoch) when the cache was created
     timestamp: u64,
+    /// `ETag` of the mirror listing the versions came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    /// `Last-Modified` of the mirror listing the versions came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+    /// Mirror the validators belong to (the default mirror when absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+}
+
+/// Outcome of a conditional fetch of the mirror's version listing
+pub enum ListingFetch {
+    /// The listing has not changed since the cached validators were issued
+    NotModified,
+    /// A fresh listing, with the validators to send next time
+    Modified {
+        /// Versions found in the listing, sorted by semantic version
+        versions: Vec<String>,
+        /// `ETag` response header, if any
+        etag: Option<String>,
+        /// `Last-Modified` response header, if any
+        last_modified: Option<String>,
+    },
 }
 
 /// Legacy cache structure for backward compatibility with chrono timestamps
@@ -64,10 +102,7 @@ impl VersionCache {
     /// * `versions` - Vector of VersionInfo to cache
     #[must_use]
     pub fn new(versions: Vec<VersionInfo>) -> Self {
-        Self {
-            versions,
-            timestamp: current_unix_timestamp(),
-        }
+        Self::with_timestamp(versions, current_unix_timestamp())
     }
 
     /// Create a version cache with a specific timestamp.
@@ -77,9 +112,52 @@ impl VersionCache {
         Self {
             versions,
             timestamp,
+            etag: None,
+            last_modified: None,
+            source: None,
         }
     }
 
+    /// Attach the HTTP validators of the listing the versions came from
+    ///
+    /// # Arguments
+    /// * `etag` - `ETag` response header, if any
+    /// * `last_modified` - `Last-Modified` response header, if any
+    #[must_use]
+    pub fn with_validators(mut self, etag: Option<String>, last_modified: Option<String>) -> Self {
+        self.etag = etag;
+        self.last_modified = last_modified;
+        self
+    }
+
+    /// Record which mirror the validators were issued by
+    ///
+    /// # Arguments
+    /// * `mirror` - Mirror base URL the listing was fetched from
+    #[must_use]
+    pub fn with_source(mut self, mirror: &str) -> Self {
+        self.source = Some(mirror.to_string());
+        self
+    }
+
+    /// Get the mirror the cached validators belong to
+    #[must_use]
+    pub fn source(&self) -> &str {
+        self.source.as_deref().unwrap_or(OC_MIRROR_BASE)
+    }
+
+    /// Get the `ETag` of the cached listing
+    #[must_use]
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Get the `Last-Modified` date of the cached listing
+    #[must_use]
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+
     /// Get version strings only (for backward compatibility)
     ///
     /// # Returns
@@ -151,15 +229,26 @@ pub fn get_cache_dir() -> Result<PathBuf, Box<dyn Error>> {
     Ok(cache_dir)
 }
 
-/// Get the full path to the version cache file
+/// Get the full path to a tool's version cache file
+///
+/// Tools sharing a listing share its cache file.
+///
+/// # Arguments
+/// * `tool` - Tool whose listing is cached
 ///
 /// # Returns
-/// Path to the versions.json cache file
+/// Path to `versions.json` for the oc listing, `versions-<tool>.json` otherwise
 ///
 /// # Errors
 /// Returns error if the cache directory cannot be created
-pub fn get_cache_file_path() -> Result<PathBuf, Box<dyn Error>> {
-    Ok(get_cache_dir()?.join("versions.json"))
+pub fn get_cache_file_path(tool: &Tool) -> Result<PathBuf, Box<dyn Error>> {
+    let owner = tool.listing_owner();
+    let file_name = if owner.is_oc() {
+        "versions.json".to_string()
+    } else {
+        format!("versions-{}.json", owner.name)
+    };
+    Ok(get_cache_dir()?.join(file_name))
 }
 
 /// Load cached version data if it exists, ignoring TTL
@@ -172,8 +261,8 @@ pub fn get_cache_file_path() -> Result<PathBuf, Box<dyn Error>> {
 ///
 /// # Errors
 /// Returns error if the cache file exists but cannot be read
-fn load_cached_versions_raw() -> Result<Option<VersionCache>, Box<dyn Error>> {
-    let cache_file = get_cache_file_path()?;
+fn load_cached_versions_raw(tool: &Tool) -> Result<Option<VersionCache>, Box<dyn Error>> {
+    let cache_file = get_cache_file_path(tool)?;
 
     if !cache_file.exists() {
         return Ok(None);
@@ -189,11 +278,11 @@ fn load_cached_versions_raw() -> Result<Option<VersionCache>, Box<dyn Error>> {
     // Try to load legacy format and migrate (uses current timestamp since old format varies)
     if let Ok(legacy_cache) = serde_json::from_str::<LegacyVersionCache>(&content) {
         // Migrate to new format with current timestamp
-        let version_info = build_version_info(&legacy_cache.versions);
+        let version_info = build_version_info_for(tool, OC_MIRROR_BASE, &legacy_cache.versions);
         let new_cache = VersionCache::new(version_info);
 
         // Save the migrated cache
-        if save_cached_versions(&new_cache.versions).is_err() {
+        if save_cached_versions(tool, &new_cache.versions).is_err() {
             // If saving fails, just continue with the migrated data
         }
 
@@ -210,13 +299,16 @@ fn load_cached_versions_raw() -> Result<Option<VersionCache>, Box<dyn Error>> {
 /// Returns `None` if the cache file doesn't exist, can't be parsed, or has
 /// exceeded the 72-hour TTL.
 ///
+/// # Arguments
+/// * `tool` - Tool whose listing is cached
+///
 /// # Returns
 /// `Some(VersionCache)` if valid, non-expired cache exists, `None` otherwise
 ///
 /// # Errors
 /// Returns error if the cache file exists but cannot be read
-pub fn load_cached_versions() -> Result<Option<VersionCache>, Box<dyn Error>> {
-    match load_cached_versions_raw()? {
+pub fn load_cached_versions(tool: &Tool) -> Result<Option<VersionCache>, Box<dyn Error>> {
+    match load_cached_versions_raw(tool)? {
         Some(cache) if cache.is_expired() => Ok(None),
         other => Ok(other),
     }
@@ -227,19 +319,24 @@ pub fn load_cached_versions() -> Result<Option<VersionCache>, Box<dyn Error>> {
 /// Serializes the version list with current timestamp and saves to cache file.
 ///
 /// # Arguments
+/// * `tool` - Tool whose listing is cached
 /// * `versions` - List of VersionInfo to cache
 ///
 /// # Errors
 /// Returns error if the cache file cannot be written
-pub fn save_cached_versions(versions: &[VersionInfo]) -> Result<(), Box<dyn Error>> {
-    let cache_file = get_cache_file_path()?;
-    let cache = VersionCache::new(versions.to_vec());
-    let content = serde_json::to_string_pretty(&cache)?;
+pub fn save_cached_versions(tool: &Tool, versions: &[VersionInfo]) -> Result<(), Box<dyn Error>> {
+    save_cache(tool, &VersionCache::new(versions.to_vec()))
+}
+
+/// Write a version cache, including its validators, to the cache file
+fn save_cache(tool: &Tool, cache: &VersionCache) -> Result<(), Box<dyn Error>> {
+    let cache_file = get_cache_file_path(tool)?;
+    let content = serde_json::to_string_pretty(cache)?;
     fs::write(&cache_file, content)?;
     Ok(())
 }
 
-/// Build version info with URLs for all supported platforms
+/// Build oc version info with URLs for all supported platforms
 ///
 /// # Arguments
 /// * `version_strings` - List of version strings
@@ -248,14 +345,36 @@ pub fn save_cached_versions(versions: &[VersionInfo]) -> Result<(), Box<dyn Erro
 /// Vector of VersionInfo with URLs populated for all platforms
 #[must_use]
 pub fn build_version_info(version_strings: &[String]) -> Vec<VersionInfo> {
-    let platforms = [Platform::LINUX_X86_64];
+    build_version_info_for(&Tool::OC, OC_MIRROR_BASE, version_strings)
+}
+
+/// Build version info with URLs on a given mirror for all supported platforms
+///
+/// The URLs are those of the listing's owner (see `Tool::listing_owner`),
+/// since the cache is shared by every tool in the listing.
+///
+/// # Arguments
+/// * `tool` - Tool whose listing the versions come from
+/// * `mirror_base` - Mirror base URL the versions were listed on
+/// * `version_strings` - List of version strings
+///
+/// # Returns
+/// Vector of VersionInfo with URLs on that mirror populated for all platforms
+#[must_use]
+pub fn build_version_info_for(
+    tool: &Tool,
+    mirror_base: &str,
+    version_strings: &[String],
+) -> Vec<VersionInfo> {
+    let platforms = Platform::ALL;
+    let owner = tool.listing_owner();
 
     version_strings
         .iter()
         .map(|version| {
             let mut urls = HashMap::new();
             for platform in &platforms {
-                let url = platform.build_download_url(version);
+                let url = owner.build_download_url_for(platform, mirror_base, version);
                 urls.insert(platform.name.to_string(), url);
             }
             VersionInfo {
@@ -266,52 +385,239 @@ pub fn build_version_info(version_strings: &[String]) -> Vec<VersionInfo> {
         .collect()
 }
 
+/// Fetch the mirror's version listing, conditionally if validators are known
+///
+/// Sends `If-None-Match`/`If-Modified-Since` built from `previous`, so an
+/// unchanged listing is answered with a cheap 304. The listing may be any
+/// format understood by `parse_version_index`. Local mirrors are listed
+/// from their directory structure instead.
+///
+/// # Arguments
+/// * `url` - URL (or local path) of the version directory listing
+/// * `previous` - Cache whose validators should be sent, if any
+///
+/// # Returns
+/// `ListingFetch::NotModified` on a 304, otherwise the parsed listing
+///
+/// # Errors
+/// Returns error if the request fails or the server answers with an error status
+pub fn fetch_version_listing(
+    url: &str,
+    previous: Option<&VersionCache>,
+) -> Result<ListingFetch, Box<dyn Error>> {
+    if let Some(dir) = local_path(url) {
+        let mut versions = list_version_directories(&dir)
+            .map_err(|e| format!("Failed to list versions: {} ({e})", dir.display()))?;
+        versions.sort_by(|a, b| compare_versions(a, b));
+        return Ok(ListingFetch::Modified {
+            versions,
+            etag: None,
+            last_modified: None,
+        });
+    }
+
+    let client = reqwest::blocking::Client::new();
+    let mut request = client.get(url);
+    if let Some(cache) = previous {
+        if let Some(etag) = cache.etag() {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = cache.last_modified() {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let resp = request.send()?;
+    if resp.status() == StatusCode::NOT_MODIFIED {
+        return Ok(ListingFetch::NotModified);
+    }
+    if !resp.status().is_success() {
+        return Err(format!("Failed to fetch versions: {url} ({})", resp.status()).into());
+    }
+
+    let header = |name| {
+        resp.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from)
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+    let content_type = header(CONTENT_TYPE);
+
+    let body = resp.text()?;
+    let mut versions = parse_version_index(&body, content_type.as_deref());
+    versions.sort_by(|a, b| compare_versions(a, b));
+
+    Ok(ListingFetch::Modified {
+        versions,
+        etag,
+        last_modified,
+    })
+}
+
 /// Fetch all versions from the API and cache them
 ///
+/// Revalidates an existing cache with a conditional request; a 304 only
+/// refreshes the cache timestamp. Mirrors are tried in their configured
+/// order until one answers.
+///
+/// # Arguments
+/// * `tool` - Tool whose versions to fetch
+/// * `verbose` - Whether to show progress information
+///
+/// # Returns
+/// Vector of available version strings sorted by semantic version
+///
+/// # Errors
+/// Returns error if no mirror can be reached or the response cannot be parsed
+pub fn fetch_and_cache_all_versions(
+    tool: &Tool,
+    verbose: bool,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    fetch_and_cache_versions_from(tool, &configured_mirrors()?, None, verbose)
+}
+
+/// Fetch versions from a list of mirrors and cache the merged result
+///
+/// The first mirror that answers provides the listing. If `wanted` is given
+/// and that listing lacks it, the remaining mirrors are consulted as well
+/// and their versions merged in, earlier mirrors winning for versions
+/// listed on several.
+///
 /// # Arguments
+/// * `tool` - Tool whose versions to fetch
+/// * `mirrors` - Mirror base URLs in order of preference
+/// * `wanted` - Version (or version prefix) the caller is looking for, if any
 /// * `verbose` - Whether to show progress information
 ///
 /// # Returns
 /// Vector of available version strings sorted by semantic version
 ///
 /// # Errors
-/// Returns error if the API request fails or the response cannot be parsed
-pub fn fetch_and_cache_all_versions(verbose: bool) -> Result<Vec<String>, Box<dyn Error>> {
+/// Returns error if every mirror fails
+pub fn fetch_and_cache_versions_from(
+    tool: &Tool,
+    mirrors: &[String],
+    wanted: Option<&str>,
+    verbose: bool,
+) -> Result<Vec<String>, Box<dyn Error>> {
     let platform = Platform::detect();
-    let url = platform.build_versions_url();
-    let body = reqwest::blocking::get(&url)?.text()?;
-
-    let mut versions = vec![];
-    for line in body.lines() {
-        if let Some(ver) = line.split('"').nth(1)
-            && ver.ends_with('/')
-            && ver.chars().next().is_some_and(|c| c.is_ascii_digit())
-        {
-            versions.push(ver.trim_end_matches('/').to_string());
+    let previous = load_cached_versions_raw(tool)?;
+
+    let mut merged: Option<VersionCache> = None;
+    let mut not_modified = false;
+    let mut errors = vec![];
+
+    for mirror in mirrors {
+        let url = tool.build_versions_url_for(&platform, mirror);
+        // Validators are only meaningful to the mirror that issued them
+        let validators = previous.as_ref().filter(|p| p.source() == mirror);
+
+        let listed = match fetch_version_listing(&url, validators) {
+            Ok(ListingFetch::NotModified) => match validators {
+                Some(previous) => {
+                    if verbose {
+                        eprintln!("Version list not modified since last fetch ({mirror})");
+                    }
+                    not_modified = merged.is_none();
+                    VersionCache::new(previous.versions.clone())
+                        .with_validators(previous.etag.clone(), previous.last_modified.clone())
+                }
+                None => {
+                    errors.push(format!("Unexpected 304 response without a cache: {url}"));
+                    continue;
+                }
+            },
+            Ok(ListingFetch::Modified {
+                versions,
+                etag,
+                last_modified,
+            }) => {
+                let versions = strip_version_prefix(tool, versions);
+                VersionCache::new(build_version_info_for(tool, mirror, &versions))
+                    .with_validators(etag, last_modified)
+            }
+            Err(e) => {
+                if verbose {
+                    eprintln!("Mirror {mirror} failed: {e}");
+                }
+                errors.push(e.to_string());
+                continue;
+            }
+        };
+
+        let cache = match merged.take() {
+            None => listed.with_source(mirror),
+            Some(mut cache) => {
+                for info in listed.versions {
+                    if !cache.has_version(&info.version) {
+                        cache.versions.push(info);
+                    }
+                }
+                cache
+            }
+        };
+
+        let satisfied = wanted.is_none_or(|wanted| {
+            cache
+                .versions
+                .iter()
+                .any(|v| matches_version_pattern(&v.version, wanted))
+        });
+        merged = Some(cache);
+        if satisfied {
+            break;
         }
     }
 
-    versions.sort_by(|a, b| compare_versions(a, b));
+    let Some(mut cache) = merged else {
+        return Err(errors.join("\n").into());
+    };
+    cache
+        .versions
+        .sort_by(|a, b| compare_versions(&a.version, &b.version));
+    let versions = cache.get_version_strings();
 
-    // Save to cache for future use
-    if let Err(e) = save_cached_versions(&build_version_info(&versions)) {
+    if let Err(e) = save_cache(tool, &cache) {
         // Don't fail the operation if caching fails, just log it in verbose mode
         if verbose {
-            eprintln!("Warning: Failed to cache versions: {e}");
+            if not_modified {
+                eprintln!("Warning: Failed to update cache timestamp: {e}");
+            } else {
+                eprintln!("Warning: Failed to cache versions: {e}");
+            }
         }
-    } else if verbose {
+    } else if verbose && !not_modified {
         eprintln!("Cached {} versions", versions.len());
     }
 
     Ok(versions)
 }
 
+/// Remove a tool's version directory prefix from listed names
+///
+/// Names without the prefix, or that don't start with a digit once it is
+/// removed, are dropped.
+fn strip_version_prefix(tool: &Tool, versions: Vec<String>) -> Vec<String> {
+    versions
+        .into_iter()
+        .filter_map(|name| {
+            name.strip_prefix(tool.version_dir_prefix)
+                .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+                .map(String::from)
+        })
+        .collect()
+}
+
 /// Update cache when a specific version is not found
 ///
 /// Fetches fresh data from the API and updates the cache, but only if the
-/// requested version is not already in the cache.
+/// requested version is not already in the cache. Mirrors after the first
+/// are consulted if the first doesn't list the version.
 ///
 /// # Arguments
+/// * `tool` - Tool whose listing is cached
 /// * `missing_version` - The version that was not found in cache
 /// * `verbose` - Whether to show progress information
 ///
@@ -321,11 +627,12 @@ pub fn fetch_and_cache_all_versions(verbose: bool) -> Result<Vec<String>, Box<dy
 /// # Errors
 /// Returns error if the API request fails or cache cannot be updated
 pub fn update_cache_for_missing_version(
+    tool: &Tool,
     missing_version: &str,
     verbose: bool,
 ) -> Result<bool, Box<dyn Error>> {
     // Check if the version is already in cache (might have been added by another process)
-    if let Some(cache) = load_cached_versions()?
+    if let Some(cache) = load_cached_versions(tool)?
         && cache.has_version(missing_version)
     {
         return Ok(false); // Version is now in cache, no update needed
@@ -336,10 +643,43 @@ pub fn update_cache_for_missing_version(
     }
 
     // Fetch fresh data and update cache
-    fetch_and_cache_all_versions(verbose)?;
+    fetch_and_cache_versions_from(tool, &configured_mirrors()?, Some(missing_version), verbose)?;
     Ok(true)
 }
 
+/// Remember the URL a version was actually downloaded from
+///
+/// Keeps the cache pointing at the mirror that served the version, so
+/// later lookups go straight there. Does nothing if the version is not
+/// cached.
+///
+/// # Arguments
+/// * `tool` - Tool that was downloaded; must own its listing
+/// * `version` - Version that was downloaded
+/// * `platform_name` - Platform the download was for
+/// * `url` - URL the archive was downloaded from
+///
+/// # Errors
+/// Returns error if the cache cannot be read or written
+pub fn record_download_url(
+    tool: &Tool,
+    version: &str,
+    platform_name: &str,
+    url: &str,
+) -> Result<(), Box<dyn Error>> {
+    let Some(mut cache) = load_cached_versions_raw(tool)? else {
+        return Ok(());
+    };
+    let Some(info) = cache.versions.iter_mut().find(|v| v.version == version) else {
+        return Ok(());
+    };
+    if info.urls.get(platform_name).map(String::as_str) == Some(url) {
+        return Ok(());
+    }
+    info.urls.insert(platform_name.to_string(), url.to_string());
+    save_cache(tool, &cache)
+}
+
 /// Format cache age in human-readable format
 ///
 /// Shows how long ago the cache was created.
@@ -376,6 +716,7 @@ pub fn format_cache_age(timestamp: u64) -> String {
 /// if the version is not found and update_if_missing is true.
 ///
 /// # Arguments
+/// * `tool` - Tool whose listing is cached
 /// * `version` - Version to check
 /// * `platform` - Platform to check for
 /// * `update_if_missing` - Whether to update cache if version not found
@@ -386,20 +727,21 @@ pub fn format_cache_age(timestamp: u64) -> String {
 /// # Errors
 /// Returns error if cache cannot be loaded or updated
 pub fn version_exists_in_cache(
+    tool: &Tool,
     version: &str,
     platform: &Platform,
     update_if_missing: bool,
 ) -> Result<Option<bool>, Box<dyn Error>> {
-    if let Some(cache) = load_cached_versions()? {
+    if let Some(cache) = load_cached_versions(tool)? {
         let exists = cache.get_download_url(version, platform.name).is_some();
         if exists || !update_if_missing {
             return Ok(Some(exists));
         }
 
         // Version not found and we should update cache
-        if update_cache_for_missing_version(version, false)? {
+        if update_cache_for_missing_version(tool, version, false)? {
             // Check again after cache update
-            if let Some(updated_cache) = load_cached_versions()? {
+            if let Some(updated_cache) = load_cached_versions(tool)? {
                 let exists_after_update = updated_cache
                     .get_download_url(version, platform.name)
                     .is_some();
@@ -417,8 +759,8 @@ pub fn version_exists_in_cache(
 ///
 /// # Errors
 /// Returns error if versions cannot be fetched from cache or API
-pub fn get_available_versions() -> Result<Vec<String>, Box<dyn Error>> {
-    get_available_versions_with_verbose(false)
+pub fn get_available_versions(tool: &Tool) -> Result<Vec<String>, Box<dyn Error>> {
+    get_available_versions_with_verbose(tool, false)
 }
 
 /// Get available versions from the OpenShift mirror with optional verbose output
@@ -427,6 +769,7 @@ pub fn get_available_versions() -> Result<Vec<String>, Box<dyn Error>> {
 /// mirror if no cache exists or the cache has expired.
 ///
 /// # Arguments
+/// * `tool` - Tool whose versions to list
 /// * `verbose` - Whether to show cache status and fetch progress
 ///
 /// # Returns
@@ -434,9 +777,12 @@ pub fn get_available_versions() -> Result<Vec<String>, Box<dyn Error>> {
 ///
 /// # Errors
 /// Returns error if versions cannot be fetched from cache or API
-pub fn get_available_versions_with_verbose(verbose: bool) -> Result<Vec<String>, Box<dyn Error>> {
+pub fn get_available_versions_with_verbose(
+    tool: &Tool,
+    verbose: bool,
+) -> Result<Vec<String>, Box<dyn Error>> {
     // Use raw loader so we can print expiry/freshness messages
-    if let Some(cache) = load_cached_versions_raw()? {
+    if let Some(cache) = load_cached_versions_raw(tool)? {
         if cache.is_expired() {
             if verbose {
                 eprintln!(
@@ -458,5 +804,44 @@ pub fn get_available_versions_with_verbose(verbose: bool) -> Result<Vec<String>,
     }
 
     // No valid cache, fetch from API
-    fetch_and_cache_all_versions(verbose)
+    fetch_and_cache_all_versions(tool, verbose)
+}
+
+/// Get available versions from the local cache only, ignoring the TTL
+///
+/// Used in offline mode, where an expired cache is still better than nothing.
+/// Never touches the network.
+///
+/// # Arguments
+/// * `tool` - Tool whose versions to list
+/// * `verbose` - Whether to show cache status
+///
+/// # Returns
+/// Vector of cached version strings sorted by semantic version
+///
+/// # Errors
+/// Returns error if no cache exists or the cache file cannot be read
+pub fn get_available_versions_offline(
+    tool: &Tool,
+    verbose: bool,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let Some(cache) = load_cached_versions_raw(tool)? else {
+        return Err("No cached version list available in offline mode.\n\
+             Run 'ovc --list <VERSION>' while online to populate the cache."
+            .into());
+    };
+
+    if verbose {
+        let state = if cache.is_expired() {
+            "expired"
+        } else {
+            "fresh"
+        };
+        eprintln!(
+            "Offline: using {state} cached versions (last updated: {})",
+            format_cache_age(cache.timestamp())
+        );
+    }
+
+    Ok(cache.get_version_strings())
 }
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! SHA-256 helpers for verifying downloaded and imported files
+//!
+//! The OpenShift mirror publishes a `sha256sum.txt` next to every release's
+//! client tarballs, in the format produced by `sha256sum(1)`. These helpers
+//! compute digests and look up expected values in such files.
+
+use std::fmt::Write as FmtWrite;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+/// Compute the lowercase hex SHA-256 digest of a byte slice
+///
+/// # Examples
+/// ```
+/// use ovc::checksum::sha256_hex;
+/// assert_eq!(
+///     sha256_hex(b""),
+///     "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+/// );
+/// ```
+#[must_use]
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    to_hex(&Sha256::digest(bytes))
+}
+
+/// Compute the lowercase hex SHA-256 digest of a file
+///
+/// The file is streamed rather than read into memory, since client
+/// tarballs are tens of megabytes.
+///
+/// # Errors
+/// Returns error if the file cannot be opened or read
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    HashingReader::new(fs::File::open(path)?).finish()
+}
+
+/// Reader adapter that computes the SHA-256 of everything read through it
+///
+/// Lets a stream be verified while it is being consumed, e.g. when a
+/// container image layer is unpacked on the fly.
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> HashingReader<R> {
+    /// Wrap a reader
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Read the rest of the stream and return its lowercase hex digest
+    ///
+    /// # Errors
+    /// Returns error if the remaining data cannot be read
+    pub fn finish(mut self) -> io::Result<String> {
+        io::copy(&mut self, &mut io::sink())?;
+        Ok(to_hex(&self.hasher.finalize()))
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+/// Find the expected digest for a file in `sha256sum(1)` output
+///
+/// Accepts both text (`<hex>  <name>`) and binary (`<hex> *<name>`) entries.
+/// Only the file name is compared, so entries with directory prefixes
+/// still match.
+///
+/// # Arguments
+/// * `sums` - Contents of a checksum file
+/// * `file_name` - Name of the file to look up
+///
+/// # Returns
+/// `Some(hex)` in lowercase if the file is listed, `None` otherwise
+///
+/// # Examples
+/// ```
+/// use ovc::checksum::find_checksum;
+/// let sums = "abc123  openshift-client-linux-4.19.0.tar.gz\n";
+/// assert_eq!(
+///     find_checksum(sums, "openshift-client-linux-4.19.0.tar.gz"),
+///     Some("abc123".to_string())
+/// );
+/// assert_eq!(find_checksum(sums, "oc.tar"), None);
+/// ```
+#[must_use]
+pub fn find_checksum(sums: &str, file_name: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (digest, name) = line.trim().split_once(char::is_whitespace)?;
+        let name = name.trim_start().trim_start_matches('*');
+        let name = name.rsplit('/').next().unwrap_or(name);
+        (name == file_name).then(|| digest.to_lowercase())
+    })
+}
+
+fn to_hex(digest: &[u8]) -> String {
+    digest.iter().fold(String::with_capacity(64), |mut s, b| {
+        write!(s, "{b:02x}").expect("write to String is infallible");
+        s
+    })
+}
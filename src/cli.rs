@@ -4,7 +4,10 @@
 // Separated from main.rs so that build.rs can include this file
 // to generate the man page via clap_mangen.
 
-use clap::Parser;
+use std::path::PathBuf;
+
+use clap::builder::BoolishValueParser;
+use clap::{Parser, Subcommand, ValueEnum};
 
 /// Standalone actions that don't require a version argument
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -12,6 +15,116 @@ pub enum StandaloneAction {
     MatchServer,
 }
 
+/// Where `--match-server` installs oc from
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ServerSource {
+    /// The cluster console's downloads route
+    Cluster,
+    /// The configured mirrors, at the version the cluster runs
+    Mirror,
+    /// The cluster, falling back to the mirrors
+    Auto,
+}
+
+/// Which installed oc `--match-server` reuses instead of downloading again
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PreferSource {
+    /// Any installed binary of the server version
+    Any,
+    /// Only binaries downloaded from a cluster
+    Cluster,
+    /// Only binaries downloaded from a mirror
+    Mirror,
+}
+
+/// Client tools `--tool` accepts, one per tool descriptor
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ToolName {
+    Oc,
+    OpenshiftInstall,
+    OcMirror,
+    Opm,
+    Butane,
+    Helm,
+}
+
+/// Subcommands for operations that take their own arguments
+#[derive(Subcommand)]
+pub enum Command {
+    /// Install oc from a local tarball or binary (path or file:// URL)
+    Import {
+        /// Tarball (.tar.gz or .tar) or oc binary to install
+        #[arg(value_name = "PATH")]
+        source: String,
+
+        /// Checksum file to verify against [default: sha256sum.txt next to PATH]
+        #[arg(long = "checksum-file", value_name = "FILE")]
+        checksum_file: Option<PathBuf>,
+
+        /// Skip checksum verification
+        #[arg(long = "no-verify", conflicts_with = "checksum_file")]
+        no_verify: bool,
+    },
+    /// Install oc from an OpenShift release image in a registry or OCI layout
+    ReleaseImage {
+        /// Release image pull spec, or oci:DIR[:TAG] for an on-disk OCI layout
+        #[arg(value_name = "IMAGE")]
+        image: String,
+
+        /// Registry credentials file (Docker config.json or pull secret)
+        #[arg(
+            long = "registry-config",
+            value_name = "FILE",
+            env = "REGISTRY_AUTH_FILE"
+        )]
+        registry_config: Option<PathBuf>,
+    },
+    /// Check that the active oc is supported against the connected cluster
+    #[command(
+        after_help = "Exit status: 0 supported, 2 warning (one minor version apart), \
+                      3 unsupported, 1 if the check could not run"
+    )]
+    Check {
+        /// Kubeconfig context to check against [default: the current context]
+        #[arg(long = "context", value_name = "CONTEXT")]
+        context: Option<String>,
+
+        /// Kubeconfig file to read [default: $KUBECONFIG or ~/.kube/config]
+        #[arg(long = "kubeconfig", value_name = "FILE")]
+        kubeconfig: Option<PathBuf>,
+    },
+    /// Switch oc to the version of the current kubeconfig context
+    Sync {
+        /// Kubeconfig context to sync to [default: the current context]
+        #[arg(long = "context", value_name = "CONTEXT")]
+        context: Option<String>,
+
+        /// Kubeconfig file to read [default: $KUBECONFIG or ~/.kube/config]
+        #[arg(long = "kubeconfig", value_name = "FILE")]
+        kubeconfig: Option<PathBuf>,
+
+        /// Ask the cluster again instead of using the version learned earlier
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Update ovc itself from its GitHub releases
+    SelfUpdate {
+        /// Only report whether a newer release is available
+        #[arg(long, conflicts_with = "version")]
+        check: bool,
+
+        /// Release to install instead of the latest (may be older)
+        #[arg(long = "version", value_name = "VERSION")]
+        version: Option<String>,
+    },
+    /// Show the configured mirrors in order of preference
+    Mirrors {
+        /// Measure each mirror's latency and save them fastest first
+        #[arg(long)]
+        probe: bool,
+    },
+}
+
 /// CLI argument parser - bools required for clap flag parsing
 #[derive(Parser)]
 #[command(
@@ -46,20 +159,131 @@ pub struct Cli {
     #[arg(short = 'm', long = "match-server", conflicts_with_all = ["list", "installed", "prune"])]
     pub match_server: bool,
 
+    /// Kubeconfig context to match [default: the current context]
+    #[arg(long = "context", value_name = "CONTEXT", requires = "match_server")]
+    pub context: Option<String>,
+
+    /// Kubeconfig file to read [default: $KUBECONFIG or ~/.kube/config]
+    #[arg(long = "kubeconfig", value_name = "FILE", requires = "match_server")]
+    pub kubeconfig: Option<PathBuf>,
+
+    /// Install one oc per distinct server version across all contexts
+    #[arg(
+        long = "all-contexts",
+        requires = "match_server",
+        conflicts_with = "context"
+    )]
+    pub all_contexts: bool,
+
+    /// Where to get the matching oc from
+    #[arg(
+        long = "source",
+        value_enum,
+        default_value_t = ServerSource::Auto,
+        requires = "match_server"
+    )]
+    pub source: ServerSource,
+
+    /// Where an installed oc must come from to be reused
+    #[arg(
+        long = "prefer",
+        value_enum,
+        default_value_t = PreferSource::Any,
+        requires = "match_server"
+    )]
+    pub prefer: PreferSource,
+
+    /// Install upstream kubectl: a version, or "match" for the active oc;
+    /// "bundled" links back to the kubectl shipped with the active oc
+    #[arg(
+        long = "kubectl",
+        value_name = "match|bundled|VERSION",
+        conflicts_with_all = ["list", "installed", "prune", "match_server", "target_version"]
+    )]
+    pub kubectl: Option<String>,
+
     /// Allow insecure TLS connections (skip certificate verification)
     #[arg(short = 'k', long = "insecure")]
     pub insecure: bool,
 
     /// Make the operation more talkative
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub verbose: bool,
 
-    /// Generate shell completion script (only bash is supported currently)
-    #[arg(long = "completion", value_name = "SHELL", value_parser = parse_completion_shell)]
+    /// Work only from local state (cached versions and installed binaries)
+    #[arg(
+        long = "offline",
+        env = "OVC_OFFLINE",
+        value_parser = BoolishValueParser::new(),
+        global = true
+    )]
+    pub offline: bool,
+
+    /// Don't update ovc automatically before running a command
+    #[arg(
+        long = "no-auto-update",
+        env = "OVC_NO_AUTO_UPDATE",
+        value_parser = BoolishValueParser::new(),
+        global = true
+    )]
+    pub no_auto_update: bool,
+
+    /// Client tool to manage
+    #[arg(
+        long = "tool",
+        value_name = "TOOL",
+        value_enum,
+        default_value_t = ToolName::Oc
+    )]
+    pub tool: ToolName,
+
+    /// Link kubectl to oc instead of the kubectl shipped in the client tarball
+    #[arg(long = "kubectl-from-oc")]
+    pub kubectl_from_oc: bool,
+
+    /// Don't reuse or keep downloaded archives in the archive cache
+    #[arg(long = "no-archive-cache")]
+    pub no_archive_cache: bool,
+
+    /// Don't generate oc and kubectl shell completion when switching versions
+    #[arg(
+        long = "no-oc-completion",
+        env = "OVC_NO_OC_COMPLETION",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub no_oc_completion: bool,
+
+    /// Print the completion script for a shell (bash, zsh, fish, elvish),
+    /// or install all of them with "install"
+    #[arg(long = "completion", value_name = "SHELL|install", value_parser = parse_completion_shell)]
     pub completion: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
 }
 
 impl Cli {
+    /// The top-level action given next to a subcommand, which can't run both
+    ///
+    /// clap's conflicts only relate arguments, so a subcommand would
+    /// otherwise silently win over e.g. `--prune` or a VERSION.
+    #[must_use]
+    pub fn action_conflicting_with_subcommand(&self) -> Option<&'static str> {
+        self.command.as_ref()?;
+        [
+            (self.version, "--version"),
+            (self.target_version.is_some(), "VERSION"),
+            (self.list.is_some(), "--list"),
+            (self.installed.is_some(), "--installed"),
+            (self.prune, "--prune"),
+            (self.match_server, "--match-server"),
+            (self.kubectl.is_some(), "--kubectl"),
+            (self.completion.is_some(), "--completion"),
+        ]
+        .into_iter()
+        .find_map(|(given, name)| given.then_some(name))
+    }
+
     #[must_use]
     pub fn standalone_action(&self) -> Option<StandaloneAction> {
         if self.match_server {
@@ -72,7 +296,9 @@ impl Cli {
 
 fn parse_completion_shell(s: &str) -> Result<String, String> {
     match s.to_lowercase().as_str() {
-        "bash" => Ok(s.to_lowercase()),
-        _ => Err(format!("unsupported shell: {s} (only 'bash' is supported)")),
+        shell @ ("bash" | "zsh" | "fish" | "elvish" | "install") => Ok(shell.to_string()),
+        _ => Err(format!(
+            "unsupported shell: {s} (supported: bash, zsh, fish, elvish, or 'install')"
+        )),
     }
 }
@@ -0,0 +1,317 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Shell completion scripts and their self-installation
+//
+// Embeds the completion scripts generated by build.rs from the clap
+// definition and installs them to the XDG locations each shell searches,
+// so completion works without a package manager.
+//
+// The generated scripts only know the CLI's flags. Each is extended with a
+// hook that completes versions (for `ovc <TAB>`, `--list` and `--installed`)
+// by running the hidden `ovc __complete <context> <prefix>` command, which
+// answers from the version cache and installed binaries only.
+//
+// Completions for the managed oc and kubectl are generated by the binaries
+// themselves (`oc completion bash`) when a version becomes active. They are
+// kept per version under `$XDG_DATA_HOME/ovc/completions/`, and the shells'
+// completion files are symlinks to the active version's, so switching back
+// to a version doesn't regenerate anything.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::manpage::get_data_dir;
+
+/// Shells with a generated completion script
+pub const SHELLS: [&str; 4] = ["bash", "zsh", "fish", "elvish"];
+
+/// Shells oc and kubectl generate completion scripts for
+pub const CLIENT_SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+/// Completion scripts embedded at compile time from build.rs output
+const BASH_COMPLETION: &str = include_str!(concat!(env!("OUT_DIR"), "/ovc.bash"));
+const ZSH_COMPLETION: &str = include_str!(concat!(env!("OUT_DIR"), "/ovc.zsh"));
+const FISH_COMPLETION: &str = include_str!(concat!(env!("OUT_DIR"), "/ovc.fish"));
+const ELVISH_COMPLETION: &str = include_str!(concat!(env!("OUT_DIR"), "/ovc.elvish"));
+
+/// Bash: wraps the generated `_ovc`, answering version positions itself
+const BASH_VERSIONS: &str = r#"
+_ovc_versions() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}"
+    local context="" word i
+    local tool_args=()
+
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        word="${COMP_WORDS[i]}"
+        case "${word}" in
+            import|release-image|check|sync|self-update|mirrors|help) _ovc "$@"; return ;;
+            --tool) tool_args=(--tool "${COMP_WORDS[i+1]}") ;;
+        esac
+    done
+
+    case "${prev}" in
+        -l|--list) context=list ;;
+        -i|--installed) context=installed ;;
+        --tool|--kubectl|--completion) ;;
+        *) [[ "${cur}" != -* ]] && context=download ;;
+    esac
+
+    if [[ -n "${context}" ]]; then
+        COMPREPLY=($(ovc __complete "${tool_args[@]}" "${context}" "${cur}" 2>/dev/null))
+        if [[ ${#COMPREPLY[@]} -gt 0 || "${context}" != download ]]; then
+            return 0
+        fi
+    fi
+    _ovc "$@"
+}
+
+complete -F _ovc_versions -o nosort -o bashdefault -o default ovc
+"#;
+
+/// Zsh: an `_arguments` action, wired to the version arguments below
+const ZSH_VERSIONS: &str = r#"
+_ovc_versions() {
+    local -a tool_args versions
+    local i=${words[(I)--tool]}
+    (( i )) && tool_args=(--tool "${words[i+1]}")
+    versions=(${(f)"$(ovc __complete "${tool_args[@]}" "$1" "$PREFIX" 2>/dev/null)"})
+    compadd -V versions -a versions
+}
+"#;
+
+/// Fish: extra `complete` rules next to the generated ones
+const FISH_VERSIONS: &str = r#"
+function __fish_ovc_versions
+    set -l tokens (commandline -opc)
+    set -l tool_args
+    if set -l i (contains -i -- --tool $tokens)
+        set tool_args --tool $tokens[(math $i + 1)]
+    end
+    ovc __complete $tool_args $argv[1] (commandline -ct) 2>/dev/null
+end
+complete -c ovc -n "__fish_ovc_needs_command" -s l -l list -x -a "(__fish_ovc_versions list)"
+complete -c ovc -n "__fish_ovc_needs_command" -s i -l installed -x -a "(__fish_ovc_versions installed)"
+complete -c ovc -n "__fish_ovc_needs_command" -f -a "(__fish_ovc_versions download)"
+"#;
+
+/// Elvish: wraps the generated arg completer
+const ELVISH_VERSIONS: &str = r#"
+use re
+var ovc-flag-completer = $edit:completion:arg-completer[ovc]
+set edit:completion:arg-completer[ovc] = {|@words|
+    var cur = $words[-1]
+    var prev = $words[-2]
+    var tool-args = []
+    for i [(range 1 (- (count $words) 1))] {
+        if (eq $words[$i] --tool) {
+            set tool-args = [--tool $words[(+ $i 1)]]
+        }
+    }
+    var context = ''
+    if (or (eq $prev -l) (eq $prev --list)) {
+        set context = list
+    } elif (or (eq $prev -i) (eq $prev --installed)) {
+        set context = installed
+    } elif (and (== (count $words) 2) (re:match '^[0-9]' $cur)) {
+        set context = download
+    }
+    if (eq $context '') {
+        $ovc-flag-completer $@words
+    } else {
+        try { ovc __complete $@tool-args $context $cur 2>/dev/null | from-lines } catch { }
+    }
+}
+"#;
+
+/// Get the completion script for a shell
+///
+/// # Returns
+/// The script, or `None` for shells without one
+#[must_use]
+pub fn completion_script(shell: &str) -> Option<String> {
+    let script = match shell {
+        "bash" => format!("{BASH_COMPLETION}{BASH_VERSIONS}"),
+        "zsh" => zsh_with_versions(ZSH_COMPLETION),
+        "fish" => format!("{FISH_COMPLETION}{FISH_VERSIONS}"),
+        "elvish" => format!("{ELVISH_COMPLETION}{ELVISH_VERSIONS}"),
+        _ => return None,
+    };
+    Some(script)
+}
+
+/// Point the generated zsh script's version arguments at `_ovc_versions`
+///
+/// clap completes plain values with `_default` (file names); the version
+/// arguments are rewritten to the matching `__complete` context.
+fn zsh_with_versions(script: &str) -> String {
+    let mut out = String::with_capacity(script.len() + ZSH_VERSIONS.len());
+    for line in script.lines() {
+        let context = if line.starts_with("'-l+[") || line.starts_with("'--list=[") {
+            Some("list")
+        } else if line.starts_with("'-i+[") || line.starts_with("'--installed=[") {
+            Some("installed")
+        } else if line.starts_with("'::target_version -- ") {
+            Some("download")
+        } else {
+            None
+        };
+        match context {
+            Some(context) => {
+                out.push_str(&line.replace(":_default'", &format!(":_ovc_versions {context}'")));
+            }
+            None => out.push_str(line),
+        }
+        out.push('\n');
+
+        if line.starts_with("autoload -U is-at-least") {
+            out.push_str(ZSH_VERSIONS);
+        }
+    }
+    out
+}
+
+/// Base directory from an XDG variable, falling back to a path under `$HOME`
+fn xdg_dir(var: &str, home_fallback: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let base = std::env::var(var)
+        .or_else(|_| std::env::var("HOME").map(|home| format!("{home}/{home_fallback}")))?;
+    Ok(PathBuf::from(base))
+}
+
+/// Get the path a shell loads ovc's completion from
+///
+/// See `get_command_completion_path`; elvish loads it from
+/// `$XDG_CONFIG_HOME/elvish/lib/ovc.elv` (with `use ovc`).
+///
+/// # Errors
+/// Returns error if the shell is unsupported or neither the XDG variable
+/// nor HOME is set
+pub fn get_completion_install_path(shell: &str) -> Result<PathBuf, Box<dyn Error>> {
+    if shell == "elvish" {
+        return Ok(xdg_dir("XDG_CONFIG_HOME", ".config")?.join("elvish/lib/ovc.elv"));
+    }
+    get_command_completion_path(shell, "ovc")
+}
+
+/// Get the path a shell loads a command's completion from
+///
+/// - bash: `$XDG_DATA_HOME/bash-completion/completions/<command>`
+///   (bash-completion loads it on demand)
+/// - zsh: `$XDG_DATA_HOME/zsh/site-functions/_<command>` (must be on `fpath`)
+/// - fish: `$XDG_CONFIG_HOME/fish/completions/<command>.fish`
+///
+/// # Errors
+/// Returns error if the shell is unsupported or neither the XDG variable
+/// nor HOME is set
+pub fn get_command_completion_path(shell: &str, command: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let path = match shell {
+        "bash" => xdg_dir("XDG_DATA_HOME", ".local/share")?
+            .join("bash-completion/completions")
+            .join(command),
+        "zsh" => xdg_dir("XDG_DATA_HOME", ".local/share")?
+            .join("zsh/site-functions")
+            .join(format!("_{command}")),
+        "fish" => xdg_dir("XDG_CONFIG_HOME", ".config")?
+            .join("fish/completions")
+            .join(format!("{command}.fish")),
+        _ => return Err(format!("unsupported shell: {shell}").into()),
+    };
+    Ok(path)
+}
+
+/// Get the path a version's completion script for a command is kept at
+///
+/// # Errors
+/// Returns error if the data directory cannot be created
+pub fn get_stored_completion_path(
+    command: &str,
+    version: &str,
+    shell: &str,
+) -> Result<PathBuf, Box<dyn Error>> {
+    Ok(get_data_dir()?
+        .join("completions")
+        .join(format!("{command}-{version}.{shell}")))
+}
+
+/// Activate the completion scripts of a client binary version
+///
+/// Generates each shell's script with `<binary> completion <shell>` unless
+/// it is already stored for this version, then points the shell's
+/// completion file for `command` at it. A shell whose script cannot be
+/// generated is skipped, keeping its previous completion.
+///
+/// # Arguments
+/// * `command` - Command the completion is for (e.g. "oc" or "kubectl")
+/// * `binary` - Binary to generate the scripts with
+/// * `version` - Version of the binary, which keys the stored scripts
+/// * `verbose` - Whether to report progress and skipped shells
+///
+/// # Errors
+/// Returns error if the stored scripts or completion links cannot be written
+pub fn activate_client_completions(
+    command: &str,
+    binary: &Path,
+    version: &str,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    for shell in CLIENT_SHELLS {
+        let stored = get_stored_completion_path(command, version, shell)?;
+        if !stored.exists() {
+            let output = Command::new(binary).args(["completion", shell]).output();
+            match output {
+                Ok(output) if output.status.success() && !output.stdout.is_empty() => {
+                    if let Some(dir) = stored.parent() {
+                        fs::create_dir_all(dir)?;
+                    }
+                    fs::write(&stored, &output.stdout)?;
+                }
+                _ => {
+                    if verbose {
+                        eprintln!(
+                            "Warning: {command} {version} did not generate {shell} completion"
+                        );
+                    }
+                    continue;
+                }
+            }
+        }
+
+        let link = get_command_completion_path(shell, command)?;
+        if let Some(dir) = link.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        if link.exists() || link.is_symlink() {
+            fs::remove_file(&link)?;
+        }
+        std::os::unix::fs::symlink(&stored, &link)?;
+    }
+
+    if verbose {
+        eprintln!("Activated {command} {version} shell completion");
+    }
+    Ok(())
+}
+
+/// Install the completion scripts for every supported shell
+///
+/// # Returns
+/// The paths written
+///
+/// # Errors
+/// Returns error if a directory or file cannot be written
+pub fn install_completions(verbose: bool) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut installed = vec![];
+    for shell in SHELLS {
+        let path = get_completion_install_path(shell)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, completion_script(shell).unwrap_or_default())?;
+
+        if verbose {
+            eprintln!("Installed {shell} completion: {}", path.display());
+        }
+        installed.push(path);
+    }
+    Ok(installed)
+}
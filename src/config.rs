@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! User configuration for ovc
+//!
+//! Settings live in `$XDG_CONFIG_HOME/ovc/config.json` (falling back to
+//! `$HOME/.config`). The file is optional; every setting has a default.
+//!
+//! ```json
+//! {
+//!   "mirrors": [
+//!     "https://artifactory.example.com/openshift-v4",
+//!     "https://mirror.openshift.com/pub/openshift-v4"
+//!   ],
+//!   "kubectl_mirror": "https://artifactory.example.com/k8s-release",
+//!   "auto_update": false,
+//!   "clusters": {
+//!     "prod": "4.16",
+//!     "https://api.dev.example.com:6443": "4.17.2"
+//!   }
+//! }
+//! ```
+//!
+//! Mirrors may also be `file://` URLs or plain paths to a local copy of the
+//! mirror tree. `clusters` maps kubeconfig context names or API server URLs
+//! to the oc version or major.minor pattern `ovc sync` switches to.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::OC_MIRROR_BASE;
+use crate::kubectl::KUBECTL_RELEASE_BASE;
+
+/// Settings read from the config file
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Config {
+    /// Mirror base URLs or local paths in order of preference, laid out like
+    /// `OC_MIRROR_BASE`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<String>,
+    /// Base URL of upstream kubectl releases, laid out like
+    /// `KUBECTL_RELEASE_BASE`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kubectl_mirror: Option<String>,
+    /// Whether ovc updates itself from GitHub releases before commands
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_update: Option<bool>,
+    /// oc version or pattern by kubeconfig context name or API server URL
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub clusters: BTreeMap<String, String>,
+}
+
+impl Config {
+    /// Load the config file, or the defaults if it doesn't exist
+    ///
+    /// # Errors
+    /// Returns error if the config file exists but cannot be read or parsed
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        Self::load_from(&get_config_file_path()?)
+    }
+
+    /// Load a config file from a specific path, or the defaults if it doesn't exist
+    ///
+    /// # Errors
+    /// Returns error if the file exists but cannot be read or parsed
+    pub fn load_from(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid config file {}: {e}", path.display()).into())
+    }
+
+    /// Save the config file, creating its directory if needed
+    ///
+    /// # Errors
+    /// Returns error if the config file cannot be written
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        self.save_to(&get_config_file_path()?)
+    }
+
+    /// Save the config to a specific path, creating its directory if needed
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be written
+    pub fn save_to(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Mirror base URLs to try, in order
+    ///
+    /// Falls back to the public OpenShift mirror when none are configured.
+    /// Trailing slashes are removed so URLs can be joined uniformly.
+    #[must_use]
+    pub fn mirrors(&self) -> Vec<String> {
+        if self.mirrors.is_empty() {
+            return vec![OC_MIRROR_BASE.to_string()];
+        }
+        self.mirrors
+            .iter()
+            .map(|m| m.trim_end_matches('/').to_string())
+            .collect()
+    }
+
+    /// Base URL to download upstream kubectl from
+    ///
+    /// Falls back to `dl.k8s.io` when none is configured.
+    #[must_use]
+    pub fn kubectl_mirror(&self) -> String {
+        self.kubectl_mirror
+            .as_deref()
+            .unwrap_or(KUBECTL_RELEASE_BASE)
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    /// Whether to update ovc automatically
+    ///
+    /// Defaults to on when not configured.
+    #[must_use]
+    pub fn auto_update(&self) -> bool {
+        self.auto_update.unwrap_or(true)
+    }
+
+    /// oc version or pattern configured for a cluster
+    ///
+    /// An entry for the context name wins over one for the API server URL.
+    ///
+    /// # Arguments
+    /// * `context` - Kubeconfig context name
+    /// * `server` - API server URL of the context
+    #[must_use]
+    pub fn cluster_version(&self, context: &str, server: &str) -> Option<&str> {
+        self.clusters
+            .get(context)
+            .or_else(|| {
+                self.clusters
+                    .iter()
+                    .find(|(key, _)| key.trim_end_matches('/') == server)
+                    .map(|(_, version)| version)
+            })
+            .map(String::as_str)
+    }
+}
+
+/// Get the ovc config directory path
+///
+/// Uses `$XDG_CONFIG_HOME` if set, otherwise falls back to `$HOME/.config`.
+/// Unlike the cache directory, it is not created until something is saved.
+///
+/// # Errors
+/// Returns error if neither `XDG_CONFIG_HOME` nor `HOME` is set
+pub fn get_config_dir() -> Result<PathBuf, Box<dyn Error>> {
+    let config_base = std::env::var("XDG_CONFIG_HOME")
+        .or_else(|_| std::env::var("HOME").map(|home| format!("{home}/.config")))?;
+    Ok(PathBuf::from(config_base).join("ovc"))
+}
+
+/// Get the full path to the config file
+///
+/// # Errors
+/// Returns error if the config directory cannot be determined
+pub fn get_config_file_path() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(get_config_dir()?.join("config.json"))
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Last-seen cluster versions of kubeconfig contexts
+//!
+//! `ovc sync` learns a context's server version once and remembers it in
+//! `$XDG_CACHE_HOME/ovc/contexts.json`, so switching back to the context
+//! later needs no API round trip:
+//!
+//! ```json
+//! {
+//!   "prod": {
+//!     "server": "https://api.prod.example.com:6443",
+//!     "version": "4.16.3"
+//!   }
+//! }
+//! ```
+//!
+//! Plain Kubernetes clusters are remembered by the OpenShift minor built on
+//! their Kubernetes version (e.g. "4.17"). An entry only applies while the
+//! context still points at the same API server. A missing or unreadable
+//! file is treated as empty.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::get_cache_dir;
+
+/// A context's API server and the version it last reported
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SeenVersion {
+    /// API server URL the context pointed at
+    pub server: String,
+    /// OpenShift version the server ran, or major.minor for plain Kubernetes
+    pub version: String,
+}
+
+/// Get the path of the learned context versions
+///
+/// # Errors
+/// Returns error if the cache directory cannot be created
+pub fn get_contexts_file_path() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(get_cache_dir()?.join("contexts.json"))
+}
+
+/// Load the learned versions by context name
+#[must_use]
+pub fn load_seen_versions(path: &Path) -> BTreeMap<String, SeenVersion> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Look up the version a context last reported
+///
+/// # Arguments
+/// * `path` - Learned versions file
+/// * `context` - Context name
+/// * `server` - API server the context points at now
+///
+/// # Returns
+/// The version, or `None` if it was never seen on this server
+#[must_use]
+pub fn lookup_seen_version(path: &Path, context: &str, server: &str) -> Option<String> {
+    load_seen_versions(path)
+        .remove(context)
+        .filter(|seen| seen.server == server)
+        .map(|seen| seen.version)
+}
+
+/// Remember the version a context reported
+///
+/// # Errors
+/// Returns error if the file cannot be written
+pub fn remember_seen_version(
+    path: &Path,
+    context: &str,
+    seen: SeenVersion,
+) -> Result<(), Box<dyn Error>> {
+    let mut versions = load_seen_versions(path);
+    versions.insert(context.to_string(), seen);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&versions)? + "\n")?;
+    Ok(())
+}
@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Parsing of mirror directory listings
+//!
+//! Mirrors expose the `clients/ocp/` directory in different ways depending on
+//! what serves them:
+//! - Apache, nginx and Artifactory autoindex HTML (`<a href="4.19.0/">`)
+//! - S3 `ListBucket` XML (`<CommonPrefixes><Prefix>` or `<Contents><Key>`)
+//! - JSON: nginx `autoindex_format json`, the Artifactory storage API, or a
+//!   plain `{"versions": [...]}` document
+//!
+//! The format is chosen from the response's `Content-Type`, falling back to
+//! sniffing the body when the header is missing or generic. Local mirrors
+//! have no listing document; their directories are read directly.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde_json::Value;
+
+/// Format of a mirror directory listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexFormat {
+    /// Autoindex HTML page with one link per entry
+    Html,
+    /// S3 `ListBucketResult` XML document
+    S3Xml,
+    /// JSON index
+    Json,
+}
+
+impl IndexFormat {
+    /// Determine the listing format from a `Content-Type` header
+    ///
+    /// # Arguments
+    /// * `content_type` - Value of the `Content-Type` header, if any
+    /// * `body` - Listing body, sniffed when the header doesn't decide
+    ///
+    /// # Examples
+    /// ```
+    /// use ovc::index::IndexFormat;
+    /// assert_eq!(
+    ///     IndexFormat::detect(Some("application/json; charset=utf-8"), "[]"),
+    ///     IndexFormat::Json
+    /// );
+    /// assert_eq!(IndexFormat::detect(None, "<?xml version=\"1.0\"?>"), IndexFormat::S3Xml);
+    /// ```
+    #[must_use]
+    pub fn detect(content_type: Option<&str>, body: &str) -> Self {
+        let mime = content_type
+            .and_then(|ct| ct.split(';').next())
+            .map(|mime| mime.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+
+        match mime.as_str() {
+            "text/html" | "application/xhtml+xml" => Self::Html,
+            "application/json" => Self::Json,
+            "application/xml" | "text/xml" => Self::S3Xml,
+            _ if mime.ends_with("+json") => Self::Json,
+            _ => Self::sniff(body),
+        }
+    }
+
+    /// Guess the listing format from the body alone
+    fn sniff(body: &str) -> Self {
+        let start = body.trim_start();
+        if start.starts_with('{') || start.starts_with('[') {
+            Self::Json
+        } else if start.starts_with("<?xml") || body.contains("<ListBucketResult") {
+            Self::S3Xml
+        } else {
+            Self::Html
+        }
+    }
+}
+
+/// Extract version directory names from a mirror listing
+///
+/// Only directory entries whose name starts with a digit (optionally
+/// after a `v`) are returned, so links such as `latest/`, `stable-4.19/` or `../` are skipped.
+/// Duplicates are removed; the order is that of the listing.
+///
+/// # Arguments
+/// * `body` - Listing body
+/// * `content_type` - Value of the `Content-Type` header, if any
+///
+/// # Returns
+/// Version strings found in the listing
+///
+/// # Examples
+/// ```
+/// use ovc::index::parse_version_index;
+/// let html = r#"<a href="latest/">latest/</a><a href='4.19.0/'>4.19.0/</a>"#;
+/// assert_eq!(parse_version_index(html, Some("text/html")), ["4.19.0"]);
+/// ```
+#[must_use]
+pub fn parse_version_index(body: &str, content_type: Option<&str>) -> Vec<String> {
+    parse_index(body, IndexFormat::detect(content_type, body))
+}
+
+/// Extract version directory names from a listing of a known format
+///
+/// # Arguments
+/// * `body` - Listing body
+/// * `format` - Format of the listing
+///
+/// # Returns
+/// Version strings found in the listing
+#[must_use]
+pub fn parse_index(body: &str, format: IndexFormat) -> Vec<String> {
+    let entries = match format {
+        IndexFormat::Html => html_directories(body),
+        IndexFormat::S3Xml => s3_directories(body),
+        IndexFormat::Json => json_directories(body),
+    };
+
+    let mut versions: Vec<String> = vec![];
+    for name in entries {
+        if is_version_name(&name) && !versions.contains(&name) {
+            versions.push(name);
+        }
+    }
+    versions
+}
+
+/// List the version directories of a local mirror
+///
+/// # Arguments
+/// * `dir` - The mirror's `clients/ocp/` directory for a platform
+///
+/// # Returns
+/// Version strings of the subdirectories, in no particular order
+///
+/// # Errors
+/// Returns error if the directory cannot be read
+pub fn list_version_directories(dir: &Path) -> io::Result<Vec<String>> {
+    let mut versions = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        // Follow symlinks, since rsynced trees often link `latest` and friends
+        if !entry.path().is_dir() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str()
+            && is_version_name(name)
+        {
+            versions.push(name.to_string());
+        }
+    }
+    Ok(versions)
+}
+
+/// Whether a directory name looks like a version
+///
+/// A leading `v` is allowed, since some tools (e.g. butane) name their
+/// version directories `v0.22.0/`.
+fn is_version_name(name: &str) -> bool {
+    name.strip_prefix('v')
+        .unwrap_or(name)
+        .starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Last path segment of a directory reference such as `a/b/4.19.0/`
+///
+/// Returns `None` for references that don't end in a slash, i.e. files.
+fn directory_name(path: &str) -> Option<String> {
+    let path = path.strip_suffix('/')?;
+    let name = path.rsplit('/').next().unwrap_or(path);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+// =============================================================================
+// HTML
+// =============================================================================
+
+/// Directory names linked from an autoindex page
+///
+/// Scans for `href` attributes anywhere in the document, so minified pages
+/// and pages with several links per line work. Absolute links and full URLs
+/// are reduced to their last path segment.
+fn html_directories(body: &str) -> Vec<String> {
+    let lower = body.to_ascii_lowercase();
+    let mut names = vec![];
+    let mut pos = 0;
+
+    while let Some(found) = lower[pos..].find("href") {
+        let start = pos + found + "href".len();
+        pos = start;
+
+        let rest = body[start..].trim_start();
+        let Some(rest) = rest.strip_prefix('=') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => rest[1..].split(quote).next().unwrap_or_default(),
+            Some(_) => rest
+                .split(|c: char| c.is_whitespace() || c == '>')
+                .next()
+                .unwrap_or_default(),
+            None => continue,
+        };
+
+        let value = value.split(['?', '#']).next().unwrap_or_default();
+        if let Some(name) = directory_name(&decode_html_entities(value)) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+/// Decode the few entities that show up in autoindex links
+fn decode_html_entities(value: &str) -> String {
+    value
+        .replace("&#43;", "+")
+        .replace("%2B", "+")
+        .replace("%2b", "+")
+        .replace("&amp;", "&")
+}
+
+// =============================================================================
+// S3 XML
+// =============================================================================
+
+/// Directory names in an S3 `ListBucketResult`
+///
+/// Listings made with a delimiter report directories as `CommonPrefixes`.
+/// Listings without one only contain object keys, in which case the
+/// directory is the first path segment below the requested prefix.
+fn s3_directories(body: &str) -> Vec<String> {
+    let mut names = vec![];
+
+    for block in element_texts(body, "CommonPrefixes") {
+        for prefix in element_texts(block, "Prefix") {
+            if let Some(name) = directory_name(prefix) {
+                names.push(name);
+            }
+        }
+    }
+
+    let request_prefix = top_level_prefix(body);
+    for key in element_texts(body, "Key") {
+        let relative = key.strip_prefix(request_prefix).unwrap_or(key);
+        if let Some((dir, _)) = relative.trim_start_matches('/').split_once('/')
+            && !dir.is_empty()
+        {
+            names.push(dir.to_string());
+        }
+    }
+
+    names
+}
+
+/// The `Prefix` the listing was requested with
+///
+/// That is the first `Prefix` element that is not part of a
+/// `CommonPrefixes` block.
+fn top_level_prefix(body: &str) -> &str {
+    let end = ["<CommonPrefixes>", "<Contents>"]
+        .iter()
+        .filter_map(|tag| body.find(tag))
+        .min()
+        .unwrap_or(body.len());
+    element_texts(&body[..end], "Prefix")
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+}
+
+/// Text content of every `<tag>...</tag>` element, in document order
+fn element_texts<'a>(body: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut texts = vec![];
+    let mut rest = body;
+
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        let Some(end) = rest.find(&close) else {
+            break;
+        };
+        texts.push(rest[..end].trim());
+        rest = &rest[end + close.len()..];
+    }
+
+    texts
+}
+
+// =============================================================================
+// JSON
+// =============================================================================
+
+/// Directory names in a JSON index
+///
+/// Accepted shapes:
+/// - `{"versions": ["4.19.0", ...]}` or a bare array of names
+/// - nginx `autoindex_format json`: `[{"name": "4.19.0", "type": "directory"}]`
+/// - Artifactory storage API: `{"children": [{"uri": "/4.19.0", "folder": true}]}`
+fn json_directories(body: &str) -> Vec<String> {
+    let Ok(value) = serde_json::from_str::<Value>(body) else {
+        return vec![];
+    };
+
+    let entries = match &value {
+        Value::Array(entries) => entries,
+        Value::Object(map) => match map.get("versions").or_else(|| map.get("children")) {
+            Some(Value::Array(entries)) => entries,
+            _ => return vec![],
+        },
+        _ => return vec![],
+    };
+
+    entries.iter().filter_map(json_entry_name).collect()
+}
+
+/// Name of a single JSON index entry, if it is a directory
+fn json_entry_name(entry: &Value) -> Option<String> {
+    match entry {
+        Value::String(name) => Some(name.trim_matches('/').to_string()),
+        Value::Object(map) => {
+            let is_dir = map.get("type").and_then(Value::as_str) == Some("directory")
+                || map.get("folder").and_then(Value::as_bool) == Some(true);
+            if !is_dir {
+                return None;
+            }
+            let name = map
+                .get("name")
+                .or_else(|| map.get("uri"))
+                .and_then(Value::as_str)?;
+            Some(name.trim_matches('/').to_string())
+        }
+        _ => None,
+    }
+}
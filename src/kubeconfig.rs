@@ -0,0 +1,366 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Reading cluster credentials from kubeconfig files
+//!
+//! Kubeconfig files are found the way kubectl finds them: the
+//! colon-separated `KUBECONFIG` list, or `~/.kube/config`. With several
+//! files, the first one defining a cluster, user or context wins, and the
+//! current context comes from the first file that sets one.
+//!
+//! Only static credentials are supported: bearer tokens (as written by
+//! `oc login`), client certificates and basic auth. Contexts that need an
+//! exec or auth-provider plugin are reported as errors.
+
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::Deserialize;
+
+/// Merged contents of the kubeconfig files
+#[derive(Deserialize, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Kubeconfig {
+    /// Context used when none is named
+    #[serde(default)]
+    pub current_context: Option<String>,
+    /// Clusters by name
+    #[serde(default)]
+    clusters: Vec<NamedCluster>,
+    /// Users by name
+    #[serde(default)]
+    users: Vec<NamedUser>,
+    /// Contexts by name
+    #[serde(default)]
+    contexts: Vec<NamedContext>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NamedCluster {
+    name: String,
+    cluster: ClusterEntry,
+}
+
+#[derive(Deserialize, Debug)]
+struct NamedUser {
+    name: String,
+    #[serde(default)]
+    user: UserEntry,
+}
+
+#[derive(Deserialize, Debug)]
+struct NamedContext {
+    name: String,
+    context: ContextEntry,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ClusterEntry {
+    server: String,
+    #[serde(default)]
+    certificate_authority: Option<PathBuf>,
+    #[serde(default)]
+    certificate_authority_data: Option<String>,
+    #[serde(default)]
+    insecure_skip_tls_verify: bool,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct UserEntry {
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(default)]
+    token_file: Option<PathBuf>,
+    #[serde(default)]
+    client_certificate: Option<PathBuf>,
+    #[serde(default)]
+    client_certificate_data: Option<String>,
+    #[serde(default)]
+    client_key: Option<PathBuf>,
+    #[serde(default)]
+    client_key_data: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    exec: Option<serde_yaml::Value>,
+    #[serde(default)]
+    auth_provider: Option<serde_yaml::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ContextEntry {
+    cluster: String,
+    #[serde(default)]
+    user: Option<String>,
+}
+
+/// Everything needed to call a cluster's API as a context's user
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Credentials {
+    /// Context the credentials come from
+    pub context: String,
+    /// API server URL (e.g. `https://api.example.com:6443`)
+    pub server: String,
+    /// PEM certificates to trust for the API server
+    pub certificate_authority: Option<Vec<u8>>,
+    /// Whether to skip verifying the API server's certificate
+    pub insecure_skip_tls_verify: bool,
+    /// Bearer token
+    pub token: Option<String>,
+    /// PEM client certificate and key
+    pub client_identity: Option<(Vec<u8>, Vec<u8>)>,
+    /// Basic auth user name and password
+    pub basic_auth: Option<(String, String)>,
+}
+
+/// Get the kubeconfig files to read, in order
+///
+/// # Errors
+/// Returns error if `KUBECONFIG` is unset and HOME is not set
+pub fn kubeconfig_paths() -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    if let Ok(list) = std::env::var("KUBECONFIG")
+        && !list.is_empty()
+    {
+        return Ok(list
+            .split(':')
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .collect());
+    }
+    let home = std::env::var("HOME")?;
+    Ok(vec![PathBuf::from(home).join(".kube/config")])
+}
+
+impl Kubeconfig {
+    /// Load and merge the kubeconfig files from `kubeconfig_paths`
+    ///
+    /// # Errors
+    /// Returns error if no file exists, or a file cannot be read or parsed
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        Self::load_from(&kubeconfig_paths()?)
+    }
+
+    /// Load and merge kubeconfig files, skipping ones that don't exist
+    ///
+    /// Relative certificate, key and token file paths are resolved against
+    /// the directory of the file that names them.
+    ///
+    /// # Errors
+    /// Returns error if none of the files exist, or one cannot be read or
+    /// parsed
+    pub fn load_from(paths: &[PathBuf]) -> Result<Self, Box<dyn Error>> {
+        let mut merged = Self::default();
+        let mut found = false;
+
+        for path in paths {
+            if !path.exists() {
+                continue;
+            }
+            found = true;
+            let content = fs::read_to_string(path)?;
+            let mut config: Self = if content.trim().is_empty() {
+                Self::default()
+            } else {
+                serde_yaml::from_str(&content)
+                    .map_err(|e| format!("Invalid kubeconfig {}: {e}", path.display()))?
+            };
+            config.resolve_paths(path.parent().unwrap_or(Path::new(".")));
+            merged.merge(config);
+        }
+
+        if !found {
+            let names: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+            return Err(format!("No kubeconfig found at {}", names.join(":")).into());
+        }
+        Ok(merged)
+    }
+
+    /// Parse a single kubeconfig document
+    ///
+    /// # Errors
+    /// Returns error if the document is not a valid kubeconfig
+    pub fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        serde_yaml::from_str(content).map_err(|e| format!("Invalid kubeconfig: {e}").into())
+    }
+
+    /// Make relative file references absolute against `dir`
+    fn resolve_paths(&mut self, dir: &Path) {
+        let resolve = |path: &mut Option<PathBuf>| {
+            if let Some(p) = path
+                && p.is_relative()
+            {
+                *p = dir.join(&*p);
+            }
+        };
+        for named in &mut self.clusters {
+            resolve(&mut named.cluster.certificate_authority);
+        }
+        for named in &mut self.users {
+            resolve(&mut named.user.token_file);
+            resolve(&mut named.user.client_certificate);
+            resolve(&mut named.user.client_key);
+        }
+    }
+
+    /// Add another file's entries, keeping the ones already defined
+    fn merge(&mut self, other: Self) {
+        if self.current_context.as_deref().unwrap_or("").is_empty() {
+            self.current_context = other.current_context;
+        }
+        for named in other.clusters {
+            if !self.clusters.iter().any(|c| c.name == named.name) {
+                self.clusters.push(named);
+            }
+        }
+        for named in other.users {
+            if !self.users.iter().any(|u| u.name == named.name) {
+                self.users.push(named);
+            }
+        }
+        for named in other.contexts {
+            if !self.contexts.iter().any(|c| c.name == named.name) {
+                self.contexts.push(named);
+            }
+        }
+    }
+
+    /// Names of all contexts, in file order
+    #[must_use]
+    pub fn context_names(&self) -> Vec<String> {
+        self.contexts.iter().map(|c| c.name.clone()).collect()
+    }
+
+    /// Find a context and its cluster
+    ///
+    /// # Arguments
+    /// * `context` - Context name, or `None` for the current context
+    fn context(
+        &self,
+        context: Option<&str>,
+    ) -> Result<(&str, &ContextEntry, &ClusterEntry), Box<dyn Error>> {
+        let name = match context {
+            Some(name) => name,
+            None => self
+                .current_context
+                .as_deref()
+                .filter(|c| !c.is_empty())
+                .ok_or("Not connected to a cluster: the kubeconfig has no current context")?,
+        };
+        let named = self
+            .contexts
+            .iter()
+            .find(|c| c.name == name)
+            .ok_or_else(|| format!("Context not found in kubeconfig: {name}"))?;
+        let entry = &named.context;
+        let cluster = &self
+            .clusters
+            .iter()
+            .find(|c| c.name == entry.cluster)
+            .ok_or_else(|| format!("Cluster {} of context {name} not found", entry.cluster))?
+            .cluster;
+        Ok((&named.name, entry, cluster))
+    }
+
+    /// Get a context's name and API server URL, without reading credentials
+    ///
+    /// # Arguments
+    /// * `context` - Context name, or `None` for the current context
+    ///
+    /// # Errors
+    /// Returns error if the context or its cluster is missing
+    pub fn server(&self, context: Option<&str>) -> Result<(String, String), Box<dyn Error>> {
+        let (name, _, cluster) = self.context(context)?;
+        Ok((
+            name.to_string(),
+            cluster.server.trim_end_matches('/').to_string(),
+        ))
+    }
+
+    /// Resolve a context's cluster and user into credentials
+    ///
+    /// # Arguments
+    /// * `context` - Context name, or `None` for the current context
+    ///
+    /// # Errors
+    /// Returns error if the context, its cluster or user is missing, the
+    /// user needs an auth plugin, or a referenced file cannot be read
+    pub fn credentials(&self, context: Option<&str>) -> Result<Credentials, Box<dyn Error>> {
+        let (name, entry, cluster) = self.context(context)?;
+
+        let certificate_authority = match (
+            &cluster.certificate_authority_data,
+            &cluster.certificate_authority,
+        ) {
+            (Some(data), _) => Some(decode_data(data, "certificate-authority-data")?),
+            (None, Some(path)) => Some(read_file(path)?),
+            (None, None) => None,
+        };
+
+        let mut credentials = Credentials {
+            context: name.to_string(),
+            server: cluster.server.trim_end_matches('/').to_string(),
+            certificate_authority,
+            insecure_skip_tls_verify: cluster.insecure_skip_tls_verify,
+            ..Credentials::default()
+        };
+
+        let Some(user_name) = entry.user.as_deref().filter(|u| !u.is_empty()) else {
+            return Ok(credentials);
+        };
+        let user = &self
+            .users
+            .iter()
+            .find(|u| u.name == user_name)
+            .ok_or_else(|| format!("User {user_name} of context {name} not found"))?
+            .user;
+
+        if user.exec.is_some() || user.auth_provider.is_some() {
+            return Err(format!(
+                "Context {name} needs an auth plugin, which ovc doesn't run; \
+                 log in with a token (oc login) instead"
+            )
+            .into());
+        }
+
+        credentials.token = match (&user.token, &user.token_file) {
+            (Some(token), _) => Some(token.clone()),
+            (None, Some(path)) => Some(String::from_utf8(read_file(path)?)?.trim().to_string()),
+            (None, None) => None,
+        };
+
+        let certificate = match (&user.client_certificate_data, &user.client_certificate) {
+            (Some(data), _) => Some(decode_data(data, "client-certificate-data")?),
+            (None, Some(path)) => Some(read_file(path)?),
+            (None, None) => None,
+        };
+        let key = match (&user.client_key_data, &user.client_key) {
+            (Some(data), _) => Some(decode_data(data, "client-key-data")?),
+            (None, Some(path)) => Some(read_file(path)?),
+            (None, None) => None,
+        };
+        credentials.client_identity = certificate.zip(key);
+
+        if let (Some(username), Some(password)) = (&user.username, &user.password) {
+            credentials.basic_auth = Some((username.clone(), password.clone()));
+        }
+
+        Ok(credentials)
+    }
+}
+
+/// Decode a base64 `*-data` field
+fn decode_data(data: &str, field: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    BASE64
+        .decode(data.trim())
+        .map_err(|e| format!("Invalid {field} in kubeconfig: {e}").into())
+}
+
+/// Read a file referenced by the kubeconfig
+fn read_file(path: &Path) -> Result<Vec<u8>, Box<dyn Error>> {
+    fs::read(path).map_err(|e| format!("Failed to read {}: {e}", path.display()).into())
+}
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Upstream kubectl releases and OpenShift/Kubernetes version skew
+//!
+//! Besides the kubectl bundled with oc, ovc can install upstream kubectl
+//! from the `dl.k8s.io` release layout:
+//!
+//! - `<base>/stable-1.30.txt` holds the latest patch of a minor (`v1.30.5`)
+//! - `<base>/v1.30.5/bin/linux/amd64/kubectl` is the binary
+//! - `<base>/v1.30.5/bin/linux/amd64/kubectl.sha256` is its digest
+//!
+//! Each OpenShift minor is built on one Kubernetes minor, and kubectl is
+//! supported within one minor of the API server, so the OpenShift minor
+//! decides which kubectl to install for `--kubectl match`.
+
+use crate::extract_major_minor;
+use crate::tool::{Packaging, Tool};
+use crate::version::{OPENSHIFT_KUBERNETES_MINORS, major_minor_numbers};
+
+/// Base URL of upstream kubectl releases
+pub const KUBECTL_RELEASE_BASE: &str = "https://dl.k8s.io/release";
+
+/// Upstream kubectl, downloaded from a release base like `KUBECTL_RELEASE_BASE`
+///
+/// Not one of `Tool::ALL`: it has no mirror listing and is installed with
+/// `--kubectl` instead of `--tool`.
+pub const KUBECTL: Tool = Tool {
+    name: "kubectl",
+    binary: "kubectl",
+    listing_template: "",
+    download_template: "v{version}/bin/{os}/{arch}/kubectl",
+    packaging: Packaging::Binary,
+    archive_members: &[],
+    version_dir_prefix: "v",
+    bundled: &[],
+};
+
+/// Get the Kubernetes minor an OpenShift version is based on
+///
+/// # Arguments
+/// * `openshift_version` - OpenShift version (e.g. "4.16.3" or "4.16")
+///
+/// # Returns
+/// Kubernetes major.minor, or `None` for versions not in the table
+///
+/// # Examples
+/// ```
+/// use ovc::kubectl::kubernetes_minor;
+/// assert_eq!(kubernetes_minor("4.16.3"), Some("1.29"));
+/// assert_eq!(kubernetes_minor("3.11.0"), None);
+/// ```
+#[must_use]
+pub fn kubernetes_minor(openshift_version: &str) -> Option<&'static str> {
+    let minor = extract_major_minor(openshift_version)?;
+    OPENSHIFT_KUBERNETES_MINORS
+        .iter()
+        .find(|(openshift, _)| *openshift == minor)
+        .map(|&(_, kubernetes)| kubernetes)
+}
+
+/// Whether a kubectl version is within supported skew of a Kubernetes version
+///
+/// kubectl is supported within one minor (older or newer) of the API server.
+///
+/// # Examples
+/// ```
+/// use ovc::kubectl::is_within_skew;
+/// assert!(is_within_skew("1.30.5", "1.29"));
+/// assert!(!is_within_skew("1.31.0", "1.29.4"));
+/// ```
+#[must_use]
+pub fn is_within_skew(kubectl_version: &str, kubernetes_version: &str) -> bool {
+    match (
+        major_minor_numbers(kubectl_version),
+        major_minor_numbers(kubernetes_version),
+    ) {
+        (Some((1, client)), Some((1, server))) => client.abs_diff(server) <= 1,
+        _ => false,
+    }
+}
+
+/// Build the URL of the file naming a minor's latest patch release
+///
+/// # Examples
+/// ```
+/// use ovc::kubectl::{KUBECTL_RELEASE_BASE, build_stable_url};
+/// assert_eq!(
+///     build_stable_url(KUBECTL_RELEASE_BASE, "1.30"),
+///     "https://dl.k8s.io/release/stable-1.30.txt"
+/// );
+/// ```
+#[must_use]
+pub fn build_stable_url(release_base: &str, minor: &str) -> String {
+    format!("{}/stable-{minor}.txt", release_base.trim_end_matches('/'))
+}
+
+/// Parse a `stable-<minor>.txt` body into a version without the `v`
+///
+/// # Returns
+/// The version (e.g. "1.30.5"), or `None` if the body isn't one
+#[must_use]
+pub fn parse_stable_version(body: &str) -> Option<String> {
+    let version = body.trim().strip_prefix('v')?;
+    (version.split('.').count() >= 3 && major_minor_numbers(version).is_some())
+        .then(|| version.to_string())
+}
+
+/// Build the URL of the digest published next to a kubectl download
+#[must_use]
+pub fn build_checksum_url(download_url: &str) -> String {
+    format!("{download_url}.sha256")
+}
+
+/// Parse a `kubectl.sha256` body
+///
+/// The file holds the bare hex digest, optionally followed by a file name
+/// in `sha256sum` style.
+///
+/// # Returns
+/// The lowercase hex digest, or `None` if the body doesn't start with one
+#[must_use]
+pub fn parse_checksum(body: &str) -> Option<String> {
+    let digest = body.split_whitespace().next()?;
+    (digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| digest.to_ascii_lowercase())
+}
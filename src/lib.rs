@@ -8,9 +8,22 @@
 //! downloading and organizing different versions of the OpenShift CLI tool.
 
 // Re-export public API from organized modules
+pub mod archives;
 pub mod cache;
+pub mod checksum;
+pub mod cluster;
+pub mod completion;
+pub mod config;
+pub mod contexts;
+pub mod index;
+pub mod kubeconfig;
+pub mod kubectl;
 pub mod manpage;
+pub mod mirror;
+pub mod oci;
 pub mod platform;
+pub mod provenance;
+pub mod tool;
 pub mod version;
 
 // Re-export commonly used items at the crate root for convenience
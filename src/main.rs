@@ -13,48 +13,119 @@
 //! - Managing installed versions locally
 //! - Pruning inactive versions
 //! - Auto-updating from GitHub releases
+//! - Offline operation from cached versions and installed binaries
+//! - Importing clients from local tarballs for air-gapped hosts
+//! - Extracting clients from OpenShift release images
+//! - Installing upstream kubectl within supported skew of oc
+//! - Managing companion tools (openshift-install, oc-mirror, opm, butane, helm)
 //! - Automatic platform detection
 //! - Version caching for improved performance
+//! - Keeping verified archives for download-free reinstalls
 
 use std::error::Error;
 use std::fs;
-use std::io;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::process::{Command, exit};
 
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, FromArgMatches};
 use flate2::read::GzDecoder;
 
 use tar::Archive;
 
 mod cli;
-use cli::{Cli, StandaloneAction};
+use cli::{Cli, Command as CliCommand, PreferSource, ServerSource, StandaloneAction, ToolName};
 
 mod update;
 
 // Import from library
+use ovc::archives::ArchiveCache;
 use ovc::cache::{
-    get_available_versions, get_available_versions_with_verbose, load_cached_versions,
-    update_cache_for_missing_version, version_exists_in_cache,
+    get_available_versions, get_available_versions_offline, get_available_versions_with_verbose,
+    load_cached_versions, record_download_url, update_cache_for_missing_version,
+    version_exists_in_cache,
 };
+use ovc::checksum::{HashingReader, find_checksum, sha256_file, sha256_hex};
+use ovc::cluster::{ClusterClient, build_console_download_url, select_download_link};
+use ovc::config::Config;
+use ovc::contexts::{
+    SeenVersion, get_contexts_file_path, lookup_seen_version, remember_seen_version,
+};
+use ovc::kubeconfig::Kubeconfig;
+use ovc::kubectl::{
+    KUBECTL, build_checksum_url, build_stable_url, is_within_skew, kubernetes_minor,
+    parse_checksum, parse_stable_version,
+};
+use ovc::mirror::{
+    configured_mirrors, fetch_bytes, fetch_reader, local_path, order_by_latency, probe_mirrors,
+};
+use ovc::oci::{RegistryAuth, extract_oc_from_release_image};
+use ovc::provenance::{self, Provenance, Source};
+use ovc::tool::{Packaging, Tool};
+use ovc::version::{Skew, classify_skew, openshift_minor};
 use ovc::{
-    OC_BIN_DIR, Platform, compare_versions, find_matching_version, is_stable_version,
-    matches_version_pattern,
+    Platform, compare_versions, find_matching_version, is_stable_version, matches_version_pattern,
 };
 
+/// Settings that affect how client binaries are resolved and installed
+#[derive(Clone, Copy)]
+struct InstallOptions {
+    /// Whether to show progress information
+    verbose: bool,
+    /// Whether to fail instead of contacting the network
+    offline: bool,
+    /// Whether to reuse and keep downloaded archives
+    archive_cache: bool,
+    /// Tool being installed
+    tool: &'static Tool,
+    /// Whether to link kubectl to oc rather than the bundled kubectl
+    kubectl_from_oc: bool,
+    /// Whether to activate the default oc's shell completion
+    oc_completion: bool,
+}
+
 /// Main application entry point
 ///
 /// Parses command line arguments and dispatches to appropriate command handlers.
 /// Ensures only one action is specified at a time and provides proper error handling.
 fn main() {
+    // Completion candidates are printed without self-update or network access.
+    // The entry point is kept out of clap so it stays out of help, the man
+    // page and the generated completion scripts.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("__complete") {
+        cmd_complete(&args[1..]);
+        return;
+    }
+
     // Install man page before parsing so --help and --version also trigger it
     ovc::manpage::ensure_man_page(false);
 
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if let (Some(action), Some(subcommand)) = (
+        cli.action_conflicting_with_subcommand(),
+        matches.subcommand_name(),
+    ) {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!("the argument '{action}' cannot be used with subcommand '{subcommand}'"),
+            )
+            .exit();
+    }
 
     // Handle completion generation first (exits immediately)
-    if cli.completion.is_some() {
-        print_bash_completion();
+    if let Some(shell) = cli.completion {
+        if shell == "install" {
+            if let Err(e) = ovc::completion::install_completions(cli.verbose) {
+                eprintln!("Failed to install completions: {e}");
+                exit(1);
+            }
+        } else if let Some(script) = ovc::completion::completion_script(&shell) {
+            print!("{script}");
+        }
         return;
     }
 
@@ -73,25 +144,105 @@ fn main() {
     let standalone = cli.standalone_action();
     let verbose = cli.verbose;
     let insecure = cli.insecure;
+    let offline = cli.offline;
+    let tool = selected_tool(cli.tool);
+    let opts = InstallOptions {
+        verbose,
+        offline,
+        archive_cache: !cli.no_archive_cache,
+        tool,
+        kubectl_from_oc: cli.kubectl_from_oc,
+        oc_completion: !cli.no_oc_completion,
+    };
 
-    update::try_auto_update(verbose);
+    // Self-update talks to api.github.com, so it is skipped entirely offline.
+    // An explicit self-update replaces the automatic one.
+    let auto_update = !cli.no_auto_update
+        && !matches!(cli.command, Some(CliCommand::SelfUpdate { .. }))
+        && Config::load().map_or(true, |config| config.auto_update());
+    if !offline && auto_update {
+        update::try_auto_update(verbose);
+    }
 
     // Dispatch to appropriate command handler
-    // Note: conflicts_with_all ensures mutual exclusivity at parse time
-    let result = if let Some(version_pattern) = cli.list {
-        cmd_list_available(&version_pattern, verbose)
+    // Note: conflicts_with_all ensures mutual exclusivity at parse time, and
+    // actions next to a subcommand were rejected right after it
+    let result = if let Some(command) = cli.command {
+        match command {
+            CliCommand::Import { .. }
+            | CliCommand::ReleaseImage { .. }
+            | CliCommand::Sync { .. }
+                if !tool.is_oc() =>
+            {
+                Err(format!("--tool {} is not supported by this command", tool.name).into())
+            }
+            CliCommand::Import {
+                source,
+                checksum_file,
+                no_verify,
+            } => cmd_import(&source, checksum_file.as_deref(), no_verify, opts),
+            CliCommand::ReleaseImage {
+                image,
+                registry_config,
+            } => cmd_release_image(&image, registry_config.as_deref(), opts),
+            CliCommand::Check {
+                context,
+                kubeconfig,
+            } => cmd_check(context.as_deref(), kubeconfig.as_deref(), insecure, opts).map(|skew| {
+                // Shell prompts and CI tell the outcomes apart by status
+                match skew {
+                    Skew::Supported => {}
+                    Skew::Warning => exit(2),
+                    Skew::Unsupported => exit(3),
+                }
+            }),
+            CliCommand::Sync {
+                context,
+                kubeconfig,
+                refresh,
+            } => cmd_sync(
+                context.as_deref(),
+                kubeconfig.as_deref(),
+                refresh,
+                insecure,
+                opts,
+            ),
+            CliCommand::SelfUpdate { .. } if offline => {
+                Err("self-update needs to reach GitHub and cannot run in offline mode".into())
+            }
+            CliCommand::SelfUpdate { check, version } => {
+                update::self_update(check, version.as_deref(), verbose)
+            }
+            CliCommand::Mirrors { probe } => cmd_mirrors(probe, offline),
+        }
+    } else if let Some(version_pattern) = cli.list {
+        cmd_list_available(tool, &version_pattern, verbose, offline)
     } else if let Some(version_pattern) = cli.installed {
-        cmd_list_installed(&version_pattern, verbose)
+        cmd_list_installed(tool, &version_pattern, verbose)
     } else if cli.prune {
-        cmd_prune(verbose)
+        cmd_prune(tool, verbose)
+    } else if let Some(spec) = cli.kubectl {
+        cmd_kubectl(&spec, opts)
     } else if let Some(action) = standalone {
         match action {
-            StandaloneAction::MatchServer => cmd_match_server(verbose, insecure),
+            StandaloneAction::MatchServer if !tool.is_oc() => {
+                Err(format!("--tool {} cannot be used with --match-server", tool.name).into())
+            }
+            StandaloneAction::MatchServer => {
+                let selection = ClusterSelection {
+                    kubeconfig: cli.kubeconfig,
+                    context: cli.context,
+                    all_contexts: cli.all_contexts,
+                    source: cli.source,
+                    prefer: cli.prefer,
+                };
+                cmd_match_server(&selection, insecure, opts)
+            }
         }
     } else {
         // Default action: download, but require a version
         match cli.target_version {
-            Some(version) => cmd_download(Some(version), verbose),
+            Some(version) => cmd_download(Some(version), opts),
             None => Err("ovc: missing version\nTry 'ovc --help' for more information.".into()),
         }
     };
@@ -107,7 +258,7 @@ fn main() {
 // Command Implementation Functions
 // =============================================================================
 
-/// Download and install a specific OpenShift client version
+/// Download and install a specific version of the selected tool
 ///
 /// This is the main download command that:
 /// 1. Resolves partial versions to full versions
@@ -117,18 +268,25 @@ fn main() {
 ///
 /// # Arguments
 /// * `version` - Optional version to download (None for latest)
-/// * `verbose` - Whether to provide detailed output
-fn cmd_download(version: Option<String>, verbose: bool) -> Result<(), Box<dyn Error>> {
+/// * `opts` - Output, network and archive cache settings
+fn cmd_download(version: Option<String>, opts: InstallOptions) -> Result<(), Box<dyn Error>> {
+    let InstallOptions {
+        verbose,
+        offline,
+        tool,
+        ..
+    } = opts;
     let input_version = match version {
         Some(v) => v,
-        None => get_latest_version()?,
+        None => get_latest_version(tool, offline)?,
     };
 
-    // Check for existing oc binary in PATH before proceeding
-    if let Some(existing_oc_path) = check_existing_oc_in_path() {
+    // Check for an existing binary of the tool in PATH before proceeding
+    if let Some(existing_path) = check_existing_in_path(tool.binary) {
         return Err(format!(
-            "Error: Remove the existing oc binary found in ${{PATH}}: {}",
-            existing_oc_path.display()
+            "Error: Remove the existing {} binary found in ${{PATH}}: {}",
+            tool.binary,
+            existing_path.display()
         )
         .into());
     }
@@ -137,14 +295,14 @@ fn cmd_download(version: Option<String>, verbose: bool) -> Result<(), Box<dyn Er
     let platform = Platform::detect();
 
     // Validate version format and resolve to full version
-    let resolved_version = resolve_version(&input_version)?;
+    let resolved_version = resolve_version(tool, &input_version, offline)?;
 
     if verbose && input_version != resolved_version {
         eprintln!("Resolved {input_version} to {resolved_version}");
     }
 
     let (path, downloaded, _download_url) =
-        ensure_oc_binary_with_platform(&resolved_version, &platform, verbose)?;
+        ensure_binary_with_platform(&resolved_version, &platform, opts)?;
 
     if verbose {
         if downloaded {
@@ -156,7 +314,7 @@ fn cmd_download(version: Option<String>, verbose: bool) -> Result<(), Box<dyn Er
     }
 
     // Always set as default
-    set_default_oc_with_platform(&resolved_version, &platform)?;
+    set_default_with_platform(&resolved_version, &platform, opts)?;
 
     if verbose {
         eprintln!("Set as default: {resolved_version}");
@@ -164,7 +322,7 @@ fn cmd_download(version: Option<String>, verbose: bool) -> Result<(), Box<dyn Er
 
     // Only show warnings in verbose mode
     if verbose {
-        check_path_warnings(verbose);
+        check_path_warnings(tool, verbose);
     }
 
     Ok(())
@@ -176,16 +334,21 @@ fn cmd_download(version: Option<String>, verbose: bool) -> Result<(), Box<dyn Er
 /// In verbose mode, also shows the full path to each binary.
 ///
 /// # Arguments
+/// * `tool` - Tool whose versions to list
 /// * `version_pattern` - Version pattern to match (e.g. "4.19")
 /// * `verbose` - Whether to show full paths
-fn cmd_list_installed(version_pattern: &str, verbose: bool) -> Result<(), Box<dyn Error>> {
+fn cmd_list_installed(
+    tool: &Tool,
+    version_pattern: &str,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
     // Validate minimum version format (must have at least major.minor)
     let parts: Vec<&str> = version_pattern.split('.').collect();
     if parts.len() < 2 {
         return Err("Version must include at least major and minor version (e.g. 4.19)".into());
     }
 
-    let all_versions = list_installed_versions()?;
+    let all_versions = list_installed_versions(tool)?;
 
     // Filter versions that match the pattern
     let matching_versions: Vec<String> = all_versions
@@ -197,10 +360,16 @@ fn cmd_list_installed(version_pattern: &str, verbose: bool) -> Result<(), Box<dy
         return Err(format!("No installed versions found matching {version_pattern}").into());
     }
 
+    let bin_dir = get_bin_dir(tool)?;
+    let manifest = provenance::load_manifest(&bin_dir);
     for version in matching_versions {
         if verbose {
-            let path = get_bin_dir()?.join(format!("oc-{version}"));
-            println!("{version} ({})", path.display());
+            let file_name = tool.file_name(&version);
+            let path = bin_dir.join(&file_name);
+            match manifest.get(&file_name) {
+                Some(p) => println!("{version} ({}, {}: {})", path.display(), p.source, p.origin),
+                None => println!("{version} ({})", path.display()),
+            }
         } else {
             println!("{version}");
         }
@@ -212,18 +381,30 @@ fn cmd_list_installed(version_pattern: &str, verbose: bool) -> Result<(), Box<dy
 ///
 /// Queries the OpenShift mirror for available versions and shows those
 /// matching the given pattern. Uses caching to improve performance.
+/// In offline mode the cache is used regardless of its age.
 ///
 /// # Arguments
+/// * `tool` - Tool whose versions to list
 /// * `version_pattern` - Version pattern to match (e.g. "4.19")
 /// * `verbose` - Whether to show cache status and other details
-fn cmd_list_available(version_pattern: &str, verbose: bool) -> Result<(), Box<dyn Error>> {
+/// * `offline` - Whether to read only from the local cache
+fn cmd_list_available(
+    tool: &Tool,
+    version_pattern: &str,
+    verbose: bool,
+    offline: bool,
+) -> Result<(), Box<dyn Error>> {
     // Validate minimum version format (must have at least major.minor)
     let parts: Vec<&str> = version_pattern.split('.').collect();
     if parts.len() < 2 {
         return Err("Version must include at least major and minor version (e.g. 4.19)".into());
     }
 
-    let all_versions = get_available_versions_with_verbose(verbose)?;
+    let all_versions = if offline {
+        get_available_versions_offline(tool, verbose)?
+    } else {
+        get_available_versions_with_verbose(tool, verbose)?
+    };
 
     // Filter versions that match the pattern
     let matching_versions: Vec<String> = all_versions
@@ -247,20 +428,20 @@ fn cmd_list_available(version_pattern: &str, verbose: bool) -> Result<(), Box<dy
 /// removed, and then removes the binary files.
 ///
 /// # Arguments
-/// * `version_pattern` - Version pattern to match (e.g. "4.19")
+/// * `tool` - Tool whose versions to remove
 /// * `verbose` - Whether to show detailed removal progress
-fn cmd_prune(verbose: bool) -> Result<(), Box<dyn Error>> {
-    let installed_versions = list_installed_versions()?;
+fn cmd_prune(tool: &Tool, verbose: bool) -> Result<(), Box<dyn Error>> {
+    let installed_versions = list_installed_versions(tool)?;
 
     if installed_versions.is_empty() {
         return Err("No installed versions found".into());
     }
 
-    // Determine the active version from the ~/.local/bin/oc symlink
-    let active_version = active_oc_version();
+    // Determine the active version from the ~/.local/bin/<binary> symlink
+    let active_version = active_version(tool);
 
-    let bin_dir = get_bin_dir()?;
-    let mut removed = 0;
+    let bin_dir = get_bin_dir(tool)?;
+    let mut removed = vec![];
     for version in &installed_versions {
         if active_version.as_deref() == Some(version.as_str()) {
             if verbose {
@@ -268,30 +449,206 @@ fn cmd_prune(verbose: bool) -> Result<(), Box<dyn Error>> {
             }
             continue;
         }
-        let oc_path = bin_dir.join(format!("oc-{version}"));
-        if oc_path.exists() {
+        let path = bin_dir.join(tool.file_name(version));
+        if path.exists() {
             if verbose {
-                eprintln!("Removing: {}", oc_path.display());
+                eprintln!("Removing: {}", path.display());
+            }
+            for (_, bundled_path) in bundled_paths(tool, &path) {
+                remove_if_exists(&bundled_path)?;
             }
-            std::fs::remove_file(&oc_path)?;
-            removed += 1;
+            std::fs::remove_file(&path)?;
+            removed.push(tool.file_name(version));
         }
     }
+    provenance::forget(&bin_dir, &removed)?;
 
     if verbose {
-        eprintln!("Removed {removed} version(s)");
+        eprintln!("Removed {} version(s)", removed.len());
     }
 
     Ok(())
 }
 
+/// Install upstream kubectl and make it the default kubectl
+///
+/// `match` picks the Kubernetes minor the active oc's OpenShift minor is
+/// based on, which keeps kubectl within supported skew of clusters of that
+/// version. Otherwise `spec` is a kubectl version; a bare major.minor
+/// resolves to its latest patch release. Downloads are verified against
+/// the published `kubectl.sha256`. `bundled` goes back to the kubectl
+/// shipped with the active oc, which switching oc otherwise leaves alone.
+///
+/// # Arguments
+/// * `spec` - `match`, `bundled` or a kubectl version (e.g. "1.30" or "1.30.5")
+/// * `opts` - Output and network settings
+fn cmd_kubectl(spec: &str, opts: InstallOptions) -> Result<(), Box<dyn Error>> {
+    let verbose = opts.verbose;
+    if spec == "bundled" {
+        let oc_version = active_version(&Tool::OC)
+            .ok_or("No active oc version. Run 'ovc [VERSION]' to install one first")?;
+        let bin_path = get_bin_dir_with_platform(&Tool::OC, &Platform::detect())?
+            .join(Tool::OC.file_name(&oc_version));
+        let (_, bundled) = bundled_paths(&Tool::OC, &bin_path)
+            .into_iter()
+            .find(|(name, _)| *name == KUBECTL.binary)
+            .filter(|(_, path)| path.exists())
+            .ok_or_else(|| format!("oc {oc_version} was installed without a bundled kubectl"))?;
+        let symlink = PathBuf::from(std::env::var("HOME")?).join(".local/bin/kubectl");
+        remove_if_exists(&symlink)?;
+        create_symlink(&bundled, &symlink)?;
+        if verbose {
+            eprintln!("Set as default: kubectl bundled with oc {oc_version}");
+        }
+        return Ok(());
+    }
+
+    let active_oc = active_version(&Tool::OC);
+    let oc_kubernetes = active_oc.as_deref().and_then(kubernetes_minor);
+    let minor_or_version = if spec == "match" {
+        let oc_version = active_oc
+            .as_deref()
+            .ok_or("No active oc version. Run 'ovc [VERSION]' to install one first")?;
+        let minor = oc_kubernetes
+            .ok_or_else(|| format!("Unknown Kubernetes version for OpenShift {oc_version}"))?;
+        if verbose {
+            eprintln!("OpenShift {oc_version} is based on Kubernetes {minor}");
+        }
+        minor.to_string()
+    } else {
+        spec.trim_start_matches('v').to_string()
+    };
+
+    let parts: Vec<&str> = minor_or_version.split('.').collect();
+    if parts.len() < 2 {
+        return Err("Version must include at least major and minor version (e.g. 1.30)".into());
+    }
+
+    let release_base = Config::load()?.kubectl_mirror();
+    let version = if parts.len() >= 3 {
+        minor_or_version
+    } else if opts.offline {
+        let installed = list_installed_versions(&KUBECTL)?;
+        find_matching_version(&minor_or_version, &installed).ok_or_else(|| {
+            format!("No installed kubectl found matching {minor_or_version} (offline mode)")
+        })?
+    } else {
+        let url = build_stable_url(&release_base, &minor_or_version);
+        let body = fetch_bytes(&url).map_err(|e| format!("Failed to resolve: {url} ({e})"))?;
+        parse_stable_version(&String::from_utf8_lossy(&body))
+            .ok_or_else(|| format!("Unexpected release version in {url}"))?
+    };
+
+    if verbose && version != spec {
+        eprintln!("Resolved kubectl {spec} to {version}");
+    }
+
+    // A matched kubectl must talk to the active oc's clusters; a requested
+    // one only gets a warning
+    if let (Some(minor), Some(oc_version)) = (oc_kubernetes, active_oc.as_deref())
+        && !is_within_skew(&version, minor)
+    {
+        let message = format!(
+            "kubectl {version} is not within one minor version of Kubernetes {minor} (oc {oc_version})"
+        );
+        if spec == "match" {
+            return Err(message.into());
+        }
+        eprintln!("Warning: {message}");
+    }
+
+    let platform = Platform::detect();
+    let bin_path =
+        get_bin_dir_with_platform(&KUBECTL, &platform)?.join(KUBECTL.file_name(&version));
+    if bin_path.exists() {
+        if verbose {
+            eprintln!(
+                "Already installed: kubectl {version} ({})",
+                bin_path.display()
+            );
+        }
+    } else if opts.offline {
+        return Err(format!(
+            "kubectl {version} is not installed and cannot be downloaded in offline mode"
+        )
+        .into());
+    } else {
+        let url = KUBECTL.build_download_url_for(&platform, &release_base, &version);
+        if verbose {
+            eprintln!("Downloading from: {url}");
+        }
+        let bytes = fetch_bytes(&url).map_err(|e| format!("Failed to download: {url} ({e})"))?;
+        let checksum_url = build_checksum_url(&url);
+        let sums = fetch_bytes(&checksum_url)
+            .map_err(|e| format!("Failed to download: {checksum_url} ({e})"))?;
+        let expected = parse_checksum(&String::from_utf8_lossy(&sums))
+            .ok_or_else(|| format!("No sha256 digest in {checksum_url}"))?;
+        let actual = sha256_hex(&bytes);
+        if actual != expected {
+            return Err(format!(
+                "sha256 mismatch for kubectl {version} (expected {expected}, got {actual})"
+            )
+            .into());
+        }
+        if verbose {
+            eprintln!("Verified sha256 of kubectl {version}");
+        }
+        install_payload(&KUBECTL, bytes.as_slice(), &bin_path)?;
+    }
+
+    let home = std::env::var("HOME")?;
+    let local_bin = PathBuf::from(&home).join(".local/bin");
+    fs::create_dir_all(&local_bin)?;
+    let symlink = local_bin.join(KUBECTL.binary);
+    remove_if_exists(&symlink)?;
+    create_symlink(&bin_path, &symlink)?;
+
+    if verbose {
+        eprintln!("Set as default: kubectl {version}");
+        check_path_warnings(&KUBECTL, verbose);
+    }
+
+    Ok(())
+}
+
+/// Which kubeconfig file and contexts `--match-server` reads
+struct ClusterSelection {
+    /// Kubeconfig file overriding `KUBECONFIG` and `~/.kube/config`
+    kubeconfig: Option<PathBuf>,
+    /// Context to use instead of the current one
+    context: Option<String>,
+    /// Whether to match every context
+    all_contexts: bool,
+    /// Where to install oc from
+    source: ServerSource,
+    /// Which installed binaries are reused
+    prefer: PreferSource,
+}
+
 /// Download and install the oc binary directly from the connected cluster
 ///
-/// Gets the console URL and downloads the oc binary from the cluster's downloads endpoint.
-/// This ensures the client version exactly matches the connected cluster.
-fn cmd_match_server(verbose: bool, insecure: bool) -> Result<(), Box<dyn Error>> {
+/// Reads the selected kubeconfig context (the current one by default), asks
+/// its API server for the console URL, and downloads oc from the console's
+/// downloads endpoint. This ensures the client version exactly matches the
+/// connected cluster. Clusters without the downloads route (HyperShift,
+/// console disabled) get the version they run from the mirror instead; see
+/// `install_for_cluster`. A matching version that is already installed is
+/// reused. With `--all-contexts`, every context is matched and the default
+/// is left alone.
+fn cmd_match_server(
+    selection: &ClusterSelection,
+    insecure: bool,
+    opts: InstallOptions,
+) -> Result<(), Box<dyn Error>> {
+    let verbose = opts.verbose;
+    if opts.offline {
+        return Err(
+            "--match-server needs to reach the cluster and cannot run in offline mode".into(),
+        );
+    }
+
     // Check for existing oc binary in PATH before proceeding
-    if let Some(existing_oc_path) = check_existing_oc_in_path() {
+    if let Some(existing_oc_path) = check_existing_in_path("oc") {
         return Err(format!(
             "Error: Remove the existing oc binary found in ${{PATH}}: {}",
             existing_oc_path.display()
@@ -299,169 +656,989 @@ fn cmd_match_server(verbose: bool, insecure: bool) -> Result<(), Box<dyn Error>>
         .into());
     }
 
-    let download_url = get_cluster_download_url(verbose)?;
+    let kubeconfig = load_kubeconfig(selection.kubeconfig.as_deref())?;
+
+    if selection.all_contexts {
+        return match_all_contexts(&kubeconfig, selection, insecure, opts);
+    }
+
+    let credentials = kubeconfig.credentials(selection.context.as_deref())?;
+    let client = ClusterClient::new(credentials, insecure)?;
+    let (version, installed) = install_for_cluster(&client, selection, opts)?;
+
+    // Set as default
+    set_default_with_platform(&version, &Platform::detect(), opts)?;
+
+    if verbose {
+        let action = if installed { "Installed" } else { "Reused" };
+        eprintln!("{action} and set as default: {version}");
+        check_path_warnings(&Tool::OC, verbose);
+    }
+
+    Ok(())
+}
+
+/// Load the given kubeconfig file, or the default ones
+fn load_kubeconfig(path: Option<&Path>) -> Result<Kubeconfig, Box<dyn Error>> {
+    match path {
+        Some(path) => Kubeconfig::load_from(&[path.to_path_buf()]),
+        None => Kubeconfig::load(),
+    }
+    .map_err(|e| format!("Not connected to a cluster. Run 'oc login' first.\n{e}").into())
+}
+
+/// Install oc for every context of the kubeconfig and print a summary
+///
+/// Contexts on the same server version share one install. Failures are
+/// listed in the summary rather than stopping the walk.
+///
+/// # Errors
+/// Returns error if any context could not be matched
+fn match_all_contexts(
+    kubeconfig: &Kubeconfig,
+    selection: &ClusterSelection,
+    insecure: bool,
+    opts: InstallOptions,
+) -> Result<(), Box<dyn Error>> {
+    let contexts = kubeconfig.context_names();
+    if contexts.is_empty() {
+        return Err("The kubeconfig has no contexts".into());
+    }
+
+    let mut rows = vec![];
+    let mut failed = 0;
+    for context in &contexts {
+        if opts.verbose {
+            eprintln!("Matching context {context}");
+        }
+        match match_context(kubeconfig, context, selection, insecure, opts) {
+            Ok((version, status)) => rows.push((context, version, status.to_string())),
+            Err(e) => {
+                failed += 1;
+                rows.push((context, "-".to_string(), format!("failed: {e}")));
+            }
+        }
+    }
+
+    let width = contexts.iter().map(String::len).max().unwrap_or(0).max(7);
+    println!("{:<width$}  {:<12}  STATUS", "CONTEXT", "VERSION");
+    for (context, version, status) in &rows {
+        println!("{context:<width$}  {version:<12}  {status}");
+    }
+
+    if failed > 0 {
+        return Err(format!(
+            "{failed} of {} contexts could not be matched",
+            contexts.len()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Install oc for one context unless its server version is already installed
+///
+/// # Returns
+/// The server version and whether it was installed or already present
+fn match_context(
+    kubeconfig: &Kubeconfig,
+    context: &str,
+    selection: &ClusterSelection,
+    insecure: bool,
+    opts: InstallOptions,
+) -> Result<(String, &'static str), Box<dyn Error>> {
+    let client = ClusterClient::new(kubeconfig.credentials(Some(context))?, insecure)?;
+    let (version, installed) = install_for_cluster(&client, selection, opts)?;
+    Ok((
+        version,
+        if installed {
+            "installed"
+        } else {
+            "already installed"
+        },
+    ))
+}
+
+/// Install the oc matching a cluster from the chosen source
+///
+/// The server version is read first, so an installed binary of that version
+/// is reused when its provenance satisfies `--prefer`. Otherwise `Auto`
+/// tries the console's downloads route first and falls back to the mirrors
+/// when the route is missing or unreachable; the mirror install uses the
+/// exact version from the cluster's `ClusterVersion`. Preferring the mirror
+/// makes `Auto` go to the mirror directly.
+///
+/// # Returns
+/// The version, and whether it was installed rather than reused
+fn install_for_cluster(
+    client: &ClusterClient,
+    selection: &ClusterSelection,
+    opts: InstallOptions,
+) -> Result<(String, bool), Box<dyn Error>> {
+    let verbose = opts.verbose;
+    let platform = Platform::detect();
+    let bin_dir = get_bin_dir_with_platform(&Tool::OC, &platform)?;
+    let (cluster_version, plain) = cluster_oc_version(client, verbose)?;
+    remember_cluster_version(client, &cluster_version, verbose);
+
+    // Plain Kubernetes only names a minor, whose latest patch is installed
+    let version = if plain {
+        let version = resolve_version(&Tool::OC, &cluster_version, opts.offline)?;
+        if verbose {
+            eprintln!("Resolved {cluster_version} to {version}");
+        }
+        version
+    } else {
+        cluster_version
+    };
+
+    let file_name = Tool::OC.file_name(&version);
+    let installed = bin_dir.join(&file_name).exists();
+    if installed {
+        let provenance = provenance::lookup(&bin_dir, &file_name);
+        if prefers(selection.prefer, provenance.as_ref()) {
+            if verbose {
+                let from =
+                    provenance.map_or_else(String::new, |p| format!(" (from the {})", p.source));
+                eprintln!("Reusing installed oc {version}{from}");
+            }
+            return Ok((version, false));
+        }
+        if verbose {
+            eprintln!("Replacing installed oc {version} from a non-preferred source");
+        }
+    }
+
+    // Plain Kubernetes has no console to download from
+    let source = match (selection.source, selection.prefer) {
+        (ServerSource::Auto, _) if plain => ServerSource::Mirror,
+        (ServerSource::Auto, PreferSource::Mirror) => ServerSource::Mirror,
+        (source, _) => source,
+    };
+
+    if source != ServerSource::Mirror {
+        match install_from_console(client, &platform, verbose) {
+            Ok(version) => return Ok((version, true)),
+            Err(e) if source == ServerSource::Auto => {
+                if verbose {
+                    eprintln!("Cluster download unavailable ({e}), using the mirror");
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    // Unless the mirror's build was preferred, it is no better than the
+    // installed one, e.g. when the preferred cluster download failed
+    if installed && selection.prefer != PreferSource::Mirror {
+        if verbose {
+            eprintln!("Reusing installed oc {version}, the mirror's build is not preferred");
+        }
+        return Ok((version, false));
+    }
+
+    if verbose {
+        eprintln!("Installing cluster version {version} from the mirror");
+    }
+    // An installed binary only gets here when it isn't the preferred mirror build
+    install_binary_with_platform(&version, &platform, opts)?;
+    Ok((version, true))
+}
+
+/// Get the oc version matching a cluster
+///
+/// OpenShift clusters report the version they run. Plain Kubernetes
+/// clusters only have `/version`, so the OpenShift minor built on their
+/// Kubernetes minor is returned as a major.minor pattern instead.
+///
+/// # Returns
+/// The version or pattern, and whether the cluster is plain Kubernetes
+fn cluster_oc_version(
+    client: &ClusterClient,
+    verbose: bool,
+) -> Result<(String, bool), Box<dyn Error>> {
+    if let Some(version) = client.openshift_version()? {
+        // Only reported, so a cluster hiding /version still matches
+        if verbose {
+            let kubernetes = client.kubernetes_version().ok();
+            eprintln!(
+                "Cluster: {} (OpenShift {version}, Kubernetes {})",
+                client.server(),
+                kubernetes.as_deref().unwrap_or("unknown")
+            );
+        }
+        return Ok((version, false));
+    }
+
+    let kubernetes = client.kubernetes_version()?;
+    if verbose {
+        eprintln!("Cluster: {} (Kubernetes {kubernetes})", client.server());
+    }
+
+    let minor = openshift_minor(&kubernetes).ok_or_else(|| {
+        format!("No OpenShift release is based on Kubernetes {kubernetes} to match the cluster")
+    })?;
+    if verbose {
+        eprintln!("Kubernetes {kubernetes} ships with OpenShift {minor}");
+    }
+    Ok((minor.to_string(), true))
+}
+
+/// Remember a cluster's version for `ovc sync`
+///
+/// Only saves a later sync the round trip, so a failure is only a warning.
+fn remember_cluster_version(client: &ClusterClient, version: &str, verbose: bool) {
+    let seen = SeenVersion {
+        server: client.server().to_string(),
+        version: version.to_string(),
+    };
+    let result = get_contexts_file_path()
+        .and_then(|path| remember_seen_version(&path, client.context(), seen));
+    if let Err(e) = result
+        && verbose
+    {
+        eprintln!(
+            "Warning: could not remember the version of {}: {e}",
+            client.context()
+        );
+    }
+}
+
+/// Whether an installed binary's provenance satisfies `--prefer`
+///
+/// Binaries without a recorded provenance only satisfy `any`.
+fn prefers(prefer: PreferSource, provenance: Option<&Provenance>) -> bool {
+    let source = provenance.map(|p| p.source);
+    match prefer {
+        PreferSource::Any => true,
+        PreferSource::Cluster => source == Some(Source::Cluster),
+        PreferSource::Mirror => source == Some(Source::Mirror),
+    }
+}
+
+/// Record where an installed binary came from
+///
+/// The binary is usable either way, so a failure is only a warning.
+fn note_provenance(
+    tool: &Tool,
+    platform: &Platform,
+    version: &str,
+    source: Source,
+    origin: &str,
+    verbose: bool,
+) {
+    let provenance = Provenance {
+        source,
+        origin: origin.to_string(),
+    };
+    let result = get_bin_dir_with_platform(tool, platform)
+        .and_then(|bin_dir| provenance::record(&bin_dir, &tool.file_name(version), provenance));
+    if let Err(e) = result
+        && verbose
+    {
+        eprintln!(
+            "Warning: Failed to record where {} {version} came from: {e}",
+            tool.binary
+        );
+    }
+}
+
+/// Download oc from a cluster's console and install it under its version
+///
+/// # Returns
+/// The version reported by the downloaded binary
+fn install_from_console(
+    client: &ClusterClient,
+    platform: &Platform,
+    verbose: bool,
+) -> Result<String, Box<dyn Error>> {
+    let download_url = get_cluster_download_url(client, platform, verbose)?;
 
     if verbose {
         eprintln!("Downloading from cluster: {download_url}");
     }
 
-    // Download to a temporary location first
+    let routes = client.route_client(&cluster_route_cas(client, verbose))?;
+    let version = install_extracted_oc(platform, "oc-cluster-temp", verbose, |temp_path| {
+        download_oc_from_cluster(&routes, &download_url, temp_path, verbose)?;
+        // A binary for another architecture must not replace a working one
+        platform.check_binary(temp_path)
+    })?;
+
+    note_provenance(
+        &Tool::OC,
+        platform,
+        &version,
+        Source::Cluster,
+        client.server(),
+        verbose,
+    );
+    Ok(version)
+}
+
+/// Get the download URL for oc binary from a cluster
+///
+/// Uses the platform's link from the console's `ConsoleCLIDownload`, which
+/// follows custom console hostnames and ingress domains. Only when the
+/// cluster doesn't publish the links is the console URL transformed to the
+/// usual downloads endpoint URL.
+fn get_cluster_download_url(
+    client: &ClusterClient,
+    platform: &Platform,
+    verbose: bool,
+) -> Result<String, Box<dyn Error>> {
+    match client.oc_download_links() {
+        Ok(Some(links)) => {
+            return select_download_link(&links, platform).ok_or_else(|| {
+                format!("The cluster offers no oc download for {}", platform.name).into()
+            });
+        }
+        Ok(None) => {}
+        Err(e) => {
+            if verbose {
+                eprintln!("Could not read the console's download links: {e}");
+            }
+        }
+    }
+
+    let console_url = client
+        .console_url()?
+        .ok_or("The cluster has no web console to download oc from")?;
+
+    if verbose {
+        eprintln!("Console URL: {console_url}");
+    }
+
+    // Example: https://console-openshift-console.apps-crc.testing
+    //       -> https://downloads-openshift-console.apps-crc.testing/amd64/linux/oc.tar
+    //          (arm64, ppc64le or s390x on those hosts)
+    Ok(build_console_download_url(&console_url, platform))
+}
+
+/// Collect the CAs to trust for a cluster's routes
+///
+/// Internal CAs sign both the API server (trusted by the kubeconfig) and,
+/// often, the ingress; the cluster also publishes the CA that signed its
+/// route certificates. Either may be missing, which only leaves the
+/// system's trust store.
+fn cluster_route_cas(client: &ClusterClient, verbose: bool) -> Vec<Vec<u8>> {
+    let mut trusted = vec![];
+    if let Some(ca) = client.certificate_authority() {
+        if verbose {
+            eprintln!("Trusting the kubeconfig's cluster CA");
+        }
+        trusted.push(ca.to_vec());
+    }
+    match client.ingress_ca() {
+        Ok(Some(ca)) => {
+            if verbose {
+                eprintln!("Trusting the cluster's ingress CA");
+            }
+            trusted.push(ca);
+        }
+        Ok(None) => {}
+        Err(e) => {
+            if verbose {
+                eprintln!("Could not read the cluster's ingress CA: {e}");
+            }
+        }
+    }
+    trusted
+}
+
+/// Download the oc binary from the cluster's downloads endpoint
+///
+/// `client` comes from `ClusterClient::route_client`, so clusters with
+/// internal CAs don't need `--insecure`, and a kubeconfig skipping TLS
+/// verification skips it here too.
+fn download_oc_from_cluster(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    dest: &Path,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let resp = match client.get(url).send() {
+        Ok(r) => r,
+        Err(e) => {
+            if verbose && e.is_connect() {
+                let err_str = e.to_string().to_lowercase();
+                if err_str.contains("certificate")
+                    || err_str.contains("ssl")
+                    || err_str.contains("tls")
+                {
+                    eprintln!(
+                        "Connection failed due to untrusted TLS certificate.\n\
+                         The cluster may be using a self-signed certificate.\n\
+                         Use --insecure (-k) to skip certificate verification."
+                    );
+                }
+            }
+            return Err(e.into());
+        }
+    };
+
+    if !resp.status().is_success() {
+        return Err(format!(
+            "Failed to download from cluster: {} ({})",
+            url,
+            resp.status()
+        )
+        .into());
+    }
+
+    let bundled = staged_bundled_paths(&Tool::OC, dest);
+    extract_binary_from_tar(resp, Tool::OC.archive_members, dest, &bundled)
+}
+
+/// Check the active oc against the version of the connected cluster
+///
+/// Prints the verdict on stdout and, unless oc is supported, the command
+/// that installs the matching one on stderr. OpenShift clusters are
+/// compared by their OpenShift version (see `classify_skew`); plain
+/// Kubernetes clusters by the Kubernetes version oc is based on, with the
+/// same minor version skew.
+///
+/// # Returns
+/// How far the active oc is from the cluster
+fn cmd_check(
+    context: Option<&str>,
+    kubeconfig: Option<&Path>,
+    insecure: bool,
+    opts: InstallOptions,
+) -> Result<Skew, Box<dyn Error>> {
+    if opts.offline {
+        return Err("check needs to reach the cluster and cannot run in offline mode".into());
+    }
+
+    let oc_version = active_version(&Tool::OC)
+        .ok_or("No active oc version. Run 'ovc --match-server' to install the matching one")?;
+    let credentials = load_kubeconfig(kubeconfig)?.credentials(context)?;
+    let client = ClusterClient::new(credentials, insecure)?;
+    if opts.verbose {
+        eprintln!("Cluster: {}", client.server());
+    }
+
+    let (skew, verdict) = if let Some(openshift) = client.openshift_version()? {
+        let skew = classify_skew(&oc_version, &openshift)
+            .ok_or_else(|| format!("Cannot compare oc {oc_version} with cluster {openshift}"))?;
+        (skew, format!("oc {oc_version}, cluster {openshift}"))
+    } else {
+        let kubernetes = client.kubernetes_version()?;
+        let minor = kubernetes_minor(&oc_version)
+            .ok_or_else(|| format!("Unknown Kubernetes version for OpenShift {oc_version}"))?;
+        let skew = classify_skew(minor, &kubernetes).ok_or_else(|| {
+            format!("Cannot compare oc {oc_version} with cluster Kubernetes {kubernetes}")
+        })?;
+        (
+            skew,
+            format!("oc {oc_version} (Kubernetes {minor}), cluster Kubernetes {kubernetes}"),
+        )
+    };
+    println!("{verdict}: {skew}");
+
+    if skew != Skew::Supported {
+        let mut command = String::from("ovc --match-server");
+        if let Some(context) = context {
+            command.push_str(&format!(" --context {context}"));
+        }
+        if let Some(path) = kubeconfig {
+            command.push_str(&format!(" --kubeconfig {}", path.display()));
+        }
+        eprintln!("Install the matching oc with: {command}");
+    }
+    Ok(skew)
+}
+
+/// Switch the active oc to the version of a kubeconfig context
+///
+/// The version comes from the config's `clusters` entry for the context or
+/// its API server, then from the version learned on an earlier sync or
+/// match-server, and only then from the cluster itself, which is remembered
+/// so switching back needs no API round trip. A major.minor pattern uses
+/// the newest installed match before resolving the latest patch.
+///
+/// # Arguments
+/// * `context` - Context to sync to, or `None` for the current one
+/// * `kubeconfig` - Kubeconfig file overriding the default ones
+/// * `refresh` - Whether to ask the cluster even if a version was learned
+/// * `insecure` - Whether to skip certificate verification
+/// * `opts` - Output, network and archive cache settings
+fn cmd_sync(
+    context: Option<&str>,
+    kubeconfig: Option<&Path>,
+    refresh: bool,
+    insecure: bool,
+    opts: InstallOptions,
+) -> Result<(), Box<dyn Error>> {
+    let verbose = opts.verbose;
+    if let Some(existing_oc_path) = check_existing_in_path("oc") {
+        return Err(format!(
+            "Error: Remove the existing oc binary found in ${{PATH}}: {}",
+            existing_oc_path.display()
+        )
+        .into());
+    }
+
+    let kubeconfig = load_kubeconfig(kubeconfig)?;
+    let (name, server) = kubeconfig.server(context)?;
+    let seen_path = get_contexts_file_path()?;
+    let config = Config::load()?;
+
+    let (version, origin) = if let Some(pattern) = config.cluster_version(&name, &server) {
+        (pattern.to_string(), "configured")
+    } else if let Some(version) =
+        lookup_seen_version(&seen_path, &name, &server).filter(|_| !refresh)
+    {
+        (version, "last seen")
+    } else {
+        if opts.offline {
+            return Err(format!(
+                "The version of context {name} is not known yet and cannot be learned in offline mode"
+            )
+            .into());
+        }
+        let client = ClusterClient::new(kubeconfig.credentials(Some(&name))?, insecure)?;
+        let (version, _) = cluster_oc_version(&client, verbose)?;
+        remember_seen_version(
+            &seen_path,
+            &name,
+            SeenVersion {
+                server: server.clone(),
+                version: version.clone(),
+            },
+        )?;
+        (version, "reported by the cluster")
+    };
+
+    if verbose {
+        eprintln!("Context {name} ({server}): oc {version} ({origin})");
+    }
+
+    // Only patterns fall back to another installed patch
+    let installed_match = if version.split('.').count() == 2 {
+        find_matching_version(&version, &list_installed_versions(&Tool::OC)?)
+    } else {
+        None
+    };
+    let resolved = match installed_match {
+        Some(installed) => installed,
+        None => resolve_version(&Tool::OC, &version, opts.offline)?,
+    };
+
     let platform = Platform::detect();
-    let bin_dir = get_bin_dir_with_platform(&platform)?;
-    let temp_path = bin_dir.join("oc-cluster-temp");
+    let (path, downloaded, _download_url) =
+        ensure_binary_with_platform(&resolved, &platform, opts)?;
+    set_default_with_platform(&resolved, &platform, opts)?;
 
-    download_oc_from_cluster(&download_url, &temp_path, insecure, verbose)?;
+    if verbose {
+        if downloaded {
+            eprintln!("Downloaded to: {}", path.display());
+        }
+        eprintln!("Set as default: {resolved}");
+        check_path_warnings(&Tool::OC, verbose);
+    }
+    Ok(())
+}
+
+/// Get the version string from an oc binary
+fn get_binary_version(path: &Path) -> Result<String, Box<dyn Error>> {
+    let output = Command::new(path)
+        .arg("version")
+        .arg("--client")
+        .output()
+        .map_err(|e| format!("Failed to run downloaded oc binary: {e}"))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Parse version from output like "Client Version: 4.16.55"
+    for line in stdout.lines() {
+        if let Some(version) = line.strip_prefix("Client Version: ") {
+            return Ok(version.trim().to_string());
+        }
+    }
+
+    Err("Could not determine version from downloaded binary".into())
+}
+
+/// Get the descriptor of the tool selected with `--tool`
+fn selected_tool(name: ToolName) -> &'static Tool {
+    match name {
+        ToolName::Oc => &Tool::OC,
+        ToolName::OpenshiftInstall => &Tool::OPENSHIFT_INSTALL,
+        ToolName::OcMirror => &Tool::OC_MIRROR,
+        ToolName::Opm => &Tool::OPM,
+        ToolName::Butane => &Tool::BUTANE,
+        ToolName::Helm => &Tool::HELM,
+    }
+}
+
+/// Print version completion candidates, one per line
+///
+/// Handles `ovc __complete [--tool TOOL] <context> [prefix]`, where
+/// `context` is `list` (available versions), `installed`, or `download`
+/// (both). Answers from the version cache and installed binaries only, so
+/// it is fast and never touches the network. Errors just mean no
+/// candidates.
+///
+/// # Arguments
+/// * `args` - Arguments after `__complete`
+fn cmd_complete(args: &[String]) {
+    let (tool, args) = match args {
+        [flag, name, rest @ ..] if flag == "--tool" => (Tool::find(name), rest),
+        _ => (Some(&Tool::OC), args),
+    };
+    let (Some(tool), [context, rest @ ..]) = (tool, args) else {
+        return;
+    };
+    let prefix = rest.first().map_or("", String::as_str);
+
+    let cached = || {
+        load_cached_versions(tool)
+            .ok()
+            .flatten()
+            .map(|cache| cache.get_version_strings())
+            .unwrap_or_default()
+    };
+    let installed = || list_installed_versions(tool).unwrap_or_default();
+
+    let mut versions = match context.as_str() {
+        "list" => cached(),
+        "installed" => installed(),
+        "download" => [cached(), installed()].concat(),
+        _ => vec![],
+    };
+    versions.retain(|v| v.starts_with(prefix));
+    versions.sort_by(|a, b| compare_versions(a, b));
+    versions.dedup();
+
+    for version in versions {
+        println!("{version}");
+    }
+}
+
+/// Show the configured mirrors, optionally probing and reordering them
+///
+/// With `probe`, each mirror's version listing is timed and the mirrors are
+/// saved to the config file fastest first, with unreachable ones last.
+///
+/// # Arguments
+/// * `probe` - Whether to measure latency and reorder the mirrors
+/// * `offline` - Whether network access is disabled
+fn cmd_mirrors(probe: bool, offline: bool) -> Result<(), Box<dyn Error>> {
+    let mut config = Config::load()?;
+    let mirrors = config.mirrors();
+
+    if !probe {
+        for mirror in &mirrors {
+            println!("{mirror}");
+        }
+        return Ok(());
+    }
+
+    if offline {
+        return Err("--probe needs to reach the mirrors and cannot run in offline mode".into());
+    }
+
+    let probes = probe_mirrors(&mirrors, &Platform::detect());
+    for probe in &probes {
+        match &probe.latency {
+            Ok(latency) => println!("{:<60} {} ms", probe.mirror, latency.as_millis()),
+            Err(e) => println!("{:<60} unreachable ({e})", probe.mirror),
+        }
+    }
+
+    let ordered = order_by_latency(&probes);
+    if ordered != mirrors {
+        config.mirrors = ordered;
+        config.save()?;
+        eprintln!("Mirror order updated");
+    }
+    Ok(())
+}
+
+/// Install an oc binary from a local tarball or binary file
+///
+/// Accepts a plain path or a `file://` URL pointing at an
+/// `openshift-client-linux-*.tar.gz` tarball, an uncompressed `oc.tar` as
+/// served by cluster download routes, or a bare oc binary. When a
+/// `sha256sum.txt` sits next to the file (or `--checksum-file` is given),
+/// the file is verified before anything is installed. The version is read
+/// from the binary itself, as `--match-server` does.
+fn cmd_import(
+    source: &str,
+    checksum_file: Option<&Path>,
+    no_verify: bool,
+    opts: InstallOptions,
+) -> Result<(), Box<dyn Error>> {
+    let verbose = opts.verbose;
+
+    // Check for existing oc binary in PATH before proceeding
+    if let Some(existing_oc_path) = check_existing_in_path("oc") {
+        return Err(format!(
+            "Error: Remove the existing oc binary found in ${{PATH}}: {}",
+            existing_oc_path.display()
+        )
+        .into());
+    }
+
+    let source_path = local_path_from_source(source)?;
+    if !source_path.is_file() {
+        return Err(format!("File not found: {}", source_path.display()).into());
+    }
+
+    if !no_verify {
+        verify_import_checksum(&source_path, checksum_file, verbose)?;
+    }
+
+    let platform = Platform::detect();
+    let version = install_extracted_oc(&platform, "oc-import-temp", verbose, |temp_path| {
+        extract_import_source(&source_path, temp_path)?;
+        platform.check_binary(temp_path)
+    })?;
+    let origin = source_path.display().to_string();
+    note_provenance(
+        &Tool::OC,
+        &platform,
+        &version,
+        Source::Import,
+        &origin,
+        verbose,
+    );
+
+    // Set as default
+    set_default_with_platform(&version, &platform, opts)?;
+
+    if verbose {
+        eprintln!("Imported and set as default: {version}");
+        check_path_warnings(&Tool::OC, verbose);
+    }
+
+    Ok(())
+}
 
-    // Get the version from the downloaded binary
-    let version = get_binary_version(&temp_path)?;
+/// Install an oc binary extracted to a temporary file under its own version
+///
+/// The version is only known once the binary can be run, so `extract`
+/// writes it to a temporary location in the bin directory first, with any
+/// bundled binaries next to it as named by `staged_bundled_paths`. They all
+/// move into place together, and are removed if extraction or version
+/// detection fails.
+///
+/// # Arguments
+/// * `platform` - Platform whose bin directory receives the binary
+/// * `temp_name` - File name for the temporary binary
+/// * `verbose` - Whether to show the detected version
+/// * `extract` - Writes the oc binary to the given path
+///
+/// # Returns
+/// The version the binary reports
+fn install_extracted_oc<F>(
+    platform: &Platform,
+    temp_name: &str,
+    verbose: bool,
+    extract: F,
+) -> Result<String, Box<dyn Error>>
+where
+    F: FnOnce(&Path) -> Result<(), Box<dyn Error>>,
+{
+    let bin_dir = get_bin_dir_with_platform(&Tool::OC, platform)?;
+    let temp_path = bin_dir.join(temp_name);
+
+    let version = extract(&temp_path)
+        .and_then(|()| get_binary_version(&temp_path))
+        .inspect_err(|_| discard_staged(&Tool::OC, &temp_path))?;
 
     if verbose {
         eprintln!("Detected version: {version}");
     }
 
-    // Move to final location with version in name
-    let final_path = bin_dir.join(format!("oc-{version}"));
-    fs::rename(&temp_path, &final_path)?;
+    // Move to final location with version in name
+    let bin_path = bin_dir.join(Tool::OC.file_name(&version));
+    commit_staged(&Tool::OC, &temp_path, &bin_path)
+        .inspect_err(|_| discard_staged(&Tool::OC, &temp_path))?;
+    Ok(version)
+}
+
+/// Install oc from an OpenShift release image
+///
+/// Resolves the release's `cli` (or `cli-artifacts`) component image and
+/// extracts oc from it, fetching only the layers needed. The version is
+/// read from the binary itself, as `import` does.
+///
+/// # Arguments
+/// * `image` - Release image pull spec, or `oci:<dir>[:<tag>]`
+/// * `registry_config` - Registry credentials file, if not the default
+/// * `opts` - Output and network settings
+fn cmd_release_image(
+    image: &str,
+    registry_config: Option<&Path>,
+    opts: InstallOptions,
+) -> Result<(), Box<dyn Error>> {
+    let verbose = opts.verbose;
+
+    // Check for existing oc binary in PATH before proceeding
+    if let Some(existing_oc_path) = check_existing_in_path("oc") {
+        return Err(format!(
+            "Error: Remove the existing oc binary found in ${{PATH}}: {}",
+            existing_oc_path.display()
+        )
+        .into());
+    }
+
+    if opts.offline && !image.starts_with("oci:") {
+        return Err(
+            "Pulling from a registry cannot run in offline mode (use oci:DIR for a local layout)"
+                .into(),
+        );
+    }
+
+    let auth = RegistryAuth::load(registry_config)?;
+    let platform = Platform::detect();
+    let version = install_extracted_oc(&platform, "oc-release-image-temp", verbose, |temp_path| {
+        extract_oc_from_release_image(image, &platform, &auth, temp_path, verbose)?;
+        set_executable(temp_path)?;
+        // A mislabeled image must not replace a working oc
+        platform.check_binary(temp_path)
+    })?;
+    note_provenance(
+        &Tool::OC,
+        &platform,
+        &version,
+        Source::ReleaseImage,
+        image,
+        verbose,
+    );
 
-    // Set as default
-    set_default_oc_with_platform(&version, &platform)?;
+    set_default_with_platform(&version, &platform, opts)?;
 
     if verbose {
-        eprintln!("Installed and set as default: {version}");
-        check_path_warnings(verbose);
+        eprintln!("Installed from release image and set as default: {version}");
+        check_path_warnings(&Tool::OC, verbose);
     }
 
     Ok(())
 }
 
-/// Get the download URL for oc binary from the connected cluster
-///
-/// Uses `oc whoami --show-console` to get the console URL, then transforms it
-/// to the downloads endpoint URL.
-fn get_cluster_download_url(verbose: bool) -> Result<String, Box<dyn Error>> {
-    let output = Command::new("oc")
-        .args(["whoami", "--show-console"])
-        .output()
-        .map_err(|e| format!("Failed to run 'oc': {e}"))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Not connected to a cluster. Run 'oc login' first.\n{stderr}").into());
-    }
-
-    let console_url = String::from_utf8_lossy(&output.stdout).trim().to_string();
-
-    if verbose {
-        eprintln!("Console URL: {console_url}");
-    }
-
-    // Transform console URL to downloads URL
-    // Example: https://console-openshift-console.apps-crc.testing
-    //       -> https://downloads-openshift-console.apps-crc.testing/amd64/linux/oc.tar
-    let download_url =
-        console_url.replace("console-openshift-console", "downloads-openshift-console");
-    let download_url = format!("{download_url}/amd64/linux/oc.tar");
-
-    Ok(download_url)
+/// Turn an import source (plain path or `file://` URL) into a local path
+fn local_path_from_source(source: &str) -> Result<PathBuf, Box<dyn Error>> {
+    local_path(source).ok_or_else(|| {
+        format!("Unsupported import source: {source} (use a local path or file:// URL)").into()
+    })
 }
 
-/// Download the oc binary from the cluster's downloads endpoint
-fn download_oc_from_cluster(
-    url: &str,
-    dest: &Path,
-    insecure: bool,
+/// Verify an import source against a `sha256sum.txt`-style checksum file
+///
+/// Without an explicit checksum file, verification only happens when a
+/// `sha256sum.txt` exists next to the source and lists it.
+fn verify_import_checksum(
+    path: &Path,
+    checksum_file: Option<&Path>,
     verbose: bool,
 ) -> Result<(), Box<dyn Error>> {
-    let client = reqwest::blocking::Client::builder()
-        .danger_accept_invalid_certs(insecure)
-        .build()?;
-
-    let resp = match client.get(url).send() {
-        Ok(r) => r,
-        Err(e) => {
-            if verbose && e.is_connect() {
-                let err_str = e.to_string().to_lowercase();
-                if err_str.contains("certificate")
-                    || err_str.contains("ssl")
-                    || err_str.contains("tls")
-                {
-                    eprintln!(
-                        "Connection failed due to untrusted TLS certificate.\n\
-                         The cluster may be using a self-signed certificate.\n\
-                         Use --insecure (-k) to skip certificate verification."
-                    );
-                }
+    let sums_path = if let Some(explicit) = checksum_file {
+        explicit.to_path_buf()
+    } else {
+        let sibling = path.with_file_name("sha256sum.txt");
+        if !sibling.is_file() {
+            if verbose {
+                eprintln!(
+                    "No sha256sum.txt next to {}, skipping verification",
+                    path.display()
+                );
             }
-            return Err(e.into());
+            return Ok(());
         }
+        sibling
     };
 
-    if !resp.status().is_success() {
-        return Err(format!(
-            "Failed to download from cluster: {} ({})",
-            url,
-            resp.status()
-        )
-        .into());
-    }
-
-    let mut archive = Archive::new(resp);
+    let sums = fs::read_to_string(&sums_path)
+        .map_err(|e| format!("Failed to read {}: {e}", sums_path.display()))?;
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
 
-    for entry in archive.entries()? {
-        let mut entry = entry?;
-        let path = entry.path()?;
-        if path.ends_with("oc") {
-            let mut out = fs::File::create(dest)?;
-            io::copy(&mut entry, &mut out)?;
-            set_executable(&dest.to_path_buf())?;
-            return Ok(());
+    let Some(expected) = find_checksum(&sums, file_name) else {
+        if checksum_file.is_some() {
+            return Err(format!("{file_name} is not listed in {}", sums_path.display()).into());
         }
-    }
+        if verbose {
+            eprintln!(
+                "{file_name} is not listed in {}, skipping verification",
+                sums_path.display()
+            );
+        }
+        return Ok(());
+    };
 
-    Err("oc binary not found in archive".into())
-}
+    let actual = sha256_file(path)?;
+    if actual != expected {
+        return Err(
+            format!("sha256 mismatch for {file_name} (expected {expected}, got {actual})").into(),
+        );
+    }
 
-/// Get the version string from an oc binary
-fn get_binary_version(path: &Path) -> Result<String, Box<dyn Error>> {
-    let output = Command::new(path)
-        .arg("version")
-        .arg("--client")
-        .output()
-        .map_err(|e| format!("Failed to run downloaded oc binary: {e}"))?;
+    if verbose {
+        eprintln!("Verified {file_name} against {}", sums_path.display());
+    }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(())
+}
 
-    // Parse version from output like "Client Version: 4.16.55"
-    for line in stdout.lines() {
-        if let Some(version) = line.strip_prefix("Client Version: ") {
-            return Ok(version.trim().to_string());
-        }
+/// Extract or copy the oc binary from an import source
+///
+/// The format is sniffed from the file contents rather than its name:
+/// gzip-compressed tarballs, plain tarballs and bare ELF binaries are
+/// supported.
+fn extract_import_source(source: &Path, dest: &Path) -> Result<(), Box<dyn Error>> {
+    let mut header = Vec::with_capacity(262);
+    fs::File::open(source)?.take(262).read_to_end(&mut header)?;
+
+    let file = fs::File::open(source)?;
+    let bundled = staged_bundled_paths(&Tool::OC, dest);
+    if header.starts_with(&[0x1f, 0x8b]) {
+        extract_binary_from_tar(
+            GzDecoder::new(file),
+            Tool::OC.archive_members,
+            dest,
+            &bundled,
+        )
+    } else if header.get(257..262) == Some(b"ustar".as_slice()) {
+        extract_binary_from_tar(file, Tool::OC.archive_members, dest, &bundled)
+    } else if header.starts_with(b"\x7fELF") {
+        fs::copy(source, dest)?;
+        set_executable(dest)
+    } else {
+        Err(format!(
+            "Unrecognized file format: {} (expected a .tar.gz, .tar or oc binary)",
+            source.display()
+        )
+        .into())
     }
-
-    Err("Could not determine version from downloaded binary".into())
 }
 
 /// Check for common PATH and installation issues
 ///
 /// Warns the user if:
-/// - The oc binary is not found in ~/.local/bin
+/// - The tool's binary is not found in ~/.local/bin
 /// - ~/.local/bin is not in the user's PATH
 ///
-/// This helps users understand why the oc command might not be available.
+/// This helps users understand why the command might not be available.
 ///
 /// # Arguments
+/// * `tool` - Tool that was installed
 /// * `verbose` - Whether to show debug information about PATH detection
-fn check_path_warnings(verbose: bool) {
+fn check_path_warnings(tool: &Tool, verbose: bool) {
     let Ok(home) = std::env::var("HOME") else {
         eprintln!("Warning: HOME environment variable not set");
         return;
     };
     let local_bin = PathBuf::from(&home).join(".local/bin");
-    let oc_symlink = local_bin.join("oc");
+    let symlink = local_bin.join(tool.binary);
 
-    // Check if oc binary exists in ~/.local/bin
-    if !oc_symlink.exists() {
-        eprintln!("Warning: oc binary not found in ~/.local/bin");
+    // Check if the binary exists in ~/.local/bin
+    if !symlink.exists() {
+        eprintln!("Warning: {} binary not found in ~/.local/bin", tool.binary);
         eprintln!("Run 'ovc [VERSION]' to install a version and set it as default");
         return;
     }
@@ -507,12 +1684,21 @@ fn check_path_warnings(verbose: bool) {
 /// patch version like "4.19.3". If the input is already a full version,
 /// returns it unchanged. Updates cache if no matching version is found.
 ///
+/// In offline mode only installed binaries are considered, since anything
+/// else would have to be downloaded.
+///
 /// # Arguments
+/// * `tool` - Tool whose versions to resolve against
 /// * `input_version` - Version string to resolve (e.g. "4.19" or "4.19.0")
+/// * `offline` - Whether to resolve against installed versions only
 ///
 /// # Returns
 /// Full version string (e.g. "4.19.3")
-fn resolve_version(input_version: &str) -> Result<String, Box<dyn Error>> {
+fn resolve_version(
+    tool: &Tool,
+    input_version: &str,
+    offline: bool,
+) -> Result<String, Box<dyn Error>> {
     // Validate minimum version format (must have at least major.minor)
     let parts: Vec<&str> = input_version.split('.').collect();
     if parts.len() < 2 {
@@ -524,16 +1710,23 @@ fn resolve_version(input_version: &str) -> Result<String, Box<dyn Error>> {
         return Ok(input_version.to_string());
     }
 
+    if offline {
+        let installed = list_installed_versions(tool)?;
+        return find_matching_version(input_version, &installed).ok_or_else(|| {
+            format!("No installed versions found matching {input_version} (offline mode)").into()
+        });
+    }
+
     // It's a partial version (major.minor), find the latest patch version
-    let mut available_versions = get_available_versions()?;
+    let mut available_versions = get_available_versions(tool)?;
 
     if let Some(latest_patch) = find_matching_version(input_version, &available_versions) {
         return Ok(latest_patch);
     }
 
     // No matching version found, try updating cache and search again
-    if update_cache_for_missing_version(input_version, false)? {
-        available_versions = get_available_versions()?;
+    if update_cache_for_missing_version(tool, input_version, false)? {
+        available_versions = get_available_versions(tool)?;
         if let Some(latest_patch) = find_matching_version(input_version, &available_versions) {
             return Ok(latest_patch);
         }
@@ -546,11 +1739,16 @@ fn resolve_version(input_version: &str) -> Result<String, Box<dyn Error>> {
 ///
 /// Fetches all available versions and returns the latest stable (non-prerelease)
 /// version. Filters out alpha, beta, rc, and other prerelease versions.
+/// In offline mode the candidates are the installed versions.
 ///
 /// # Returns
 /// Latest stable version string
-fn get_latest_version() -> Result<String, Box<dyn Error>> {
-    let versions = get_available_versions()?;
+fn get_latest_version(tool: &Tool, offline: bool) -> Result<String, Box<dyn Error>> {
+    let versions = if offline {
+        list_installed_versions(tool)?
+    } else {
+        get_available_versions(tool)?
+    };
 
     // Filter out pre-release versions (rc, alpha, beta, nightly, etc.)
     let stable_versions: Vec<String> = versions
@@ -568,44 +1766,96 @@ fn get_latest_version() -> Result<String, Box<dyn Error>> {
 // Binary Management Functions
 // =============================================================================
 
-/// Ensure the OpenShift client binary is available for the specified version and platform
+/// Ensure the selected tool's binary is available for the specified version and platform
 ///
-/// Checks if the binary already exists locally. If not, downloads and extracts it.
-/// Returns information about the binary path and whether a download occurred.
+/// Checks if the binary already exists locally. If not, installs it from the
+/// archive cache or downloads and extracts it. Returns information about the
+/// binary path and whether an install occurred.
 /// Uses cached URLs when available to avoid rebuilding URLs.
 ///
 /// # Arguments
 /// * `version` - Version to ensure is available
 /// * `platform` - Target platform for the binary
-/// * `verbose` - Whether to show download progress
+/// * `opts` - Output, network and archive cache settings, and the tool
 ///
 /// # Returns
 /// Tuple of (binary_path, was_downloaded, download_url)
-fn ensure_oc_binary_with_platform(
+fn ensure_binary_with_platform(
     version: &str,
     platform: &Platform,
-    verbose: bool,
+    opts: InstallOptions,
 ) -> Result<(PathBuf, bool, String), Box<dyn Error>> {
-    let bin_dir = get_bin_dir_with_platform(platform)?;
-    let oc_path = bin_dir.join(format!("oc-{version}"));
-
-    // Try to get URL from cache first, fallback to building it
-    let download_url = if let Some(cache) = load_cached_versions()? {
-        cache
-            .get_download_url(version, platform.name)
-            .unwrap_or_else(|| platform.build_download_url(version))
-    } else {
-        platform.build_download_url(version)
-    };
+    let tool = opts.tool;
+    let bin_dir = get_bin_dir_with_platform(tool, platform)?;
+    let bin_path = bin_dir.join(tool.file_name(version));
+
+    // Prefer the URL the cache remembers, falling back to the first mirror
+    let download_url = candidate_download_urls(tool, version, platform)?.remove(0);
+
+    if bin_path.exists() {
+        return Ok((bin_path, false, download_url)); // false = no download performed
+    }
+
+    let download_url = install_binary_with_platform(version, platform, opts)?;
+    Ok((bin_path, true, download_url)) // true = download performed
+}
+
+/// Install the selected tool's binary for a version, even if it is installed
+///
+/// The binary is staged next to its final path and only moved into place
+/// once it is complete and verified, so an installed binary of the same
+/// version keeps working if the install fails.
+///
+/// # Arguments
+/// * `version` - Version to install
+/// * `platform` - Target platform for the binary
+/// * `opts` - Output, network and archive cache settings, and the tool
+///
+/// # Returns
+/// The URL the binary was (or would have been) downloaded from
+fn install_binary_with_platform(
+    version: &str,
+    platform: &Platform,
+    opts: InstallOptions,
+) -> Result<String, Box<dyn Error>> {
+    let tool = opts.tool;
+    let bin_path = get_bin_dir_with_platform(tool, platform)?.join(tool.file_name(version));
+    let staged_path = staged_path(&bin_path);
+    let download_url = candidate_download_urls(tool, version, platform)?.remove(0);
+
+    // A previously downloaded archive makes the mirror unnecessary, even offline
+    let cache_name = tool.archive_cache_name(platform, version);
+    if opts.archive_cache
+        && let Some(archive) = cached_archive(&cache_name, opts.verbose)
+    {
+        if opts.verbose {
+            eprintln!("Installing from cached archive: {}", archive.display());
+        }
+        install_payload(tool, fs::File::open(&archive)?, &staged_path)
+            .and_then(|()| commit_staged(tool, &staged_path, &bin_path))
+            .inspect_err(|_| discard_staged(tool, &staged_path))?;
+        note_provenance(
+            tool,
+            platform,
+            version,
+            Source::Mirror,
+            &download_url,
+            opts.verbose,
+        );
+        return Ok(download_url);
+    }
 
-    if oc_path.exists() {
-        return Ok((oc_path, false, download_url)); // false = no download performed
+    if opts.offline {
+        return Err(format!(
+            "Version {version} is not installed and cannot be downloaded in offline mode"
+        )
+        .into());
     }
 
     // Check if version exists, preferring cache lookup with update if missing
-    let version_exists = match version_exists_in_cache(version, platform, true)? {
+    let version_exists = match version_exists_in_cache(tool, version, platform, true)? {
         Some(exists) => exists,
-        None => version_exists_on_mirror(version, platform)?,
+        None => version_exists_on_mirror(tool, version, platform)?,
     };
 
     if !version_exists {
@@ -616,93 +1866,407 @@ fn ensure_oc_binary_with_platform(
         .into());
     }
 
-    if verbose {
-        eprintln!("Downloading from: {download_url}");
+    // The existence check may have refreshed the cache from another mirror
+    let urls = candidate_download_urls(tool, version, platform)?;
+    let download_url = download_and_extract_from_mirrors(&staged_path, &urls, &cache_name, opts)
+        .and_then(|url| commit_staged(tool, &staged_path, &bin_path).map(|()| url))
+        .inspect_err(|_| discard_staged(tool, &staged_path))?;
+    if owns_listing(tool)
+        && let Err(e) = record_download_url(tool, version, platform.name, &download_url)
+        && opts.verbose
+    {
+        eprintln!("Warning: Failed to record download mirror: {e}");
+    }
+    note_provenance(
+        tool,
+        platform,
+        version,
+        Source::Mirror,
+        &download_url,
+        opts.verbose,
+    );
+    Ok(download_url)
+}
+
+/// Get the URLs a version can be downloaded from, in the order to try them
+///
+/// The URL remembered in the cache comes first, followed by the version's
+/// URL on each configured mirror. Cached URLs are only used by the tool
+/// that owns the listing, since they point at that tool's downloads.
+///
+/// # Returns
+/// Non-empty list of distinct download URLs
+fn candidate_download_urls(
+    tool: &Tool,
+    version: &str,
+    platform: &Platform,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut urls = vec![];
+    if owns_listing(tool)
+        && let Some(cache) = load_cached_versions(tool)?
+        && let Some(url) = cache.get_download_url(version, platform.name)
+    {
+        urls.push(url);
+    }
+    for mirror in configured_mirrors()? {
+        let url = tool.build_download_url_for(platform, &mirror, version);
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
     }
-    download_and_extract_with_url(version, &oc_path, &download_url)?;
-    Ok((oc_path, true, download_url)) // true = download performed
+    Ok(urls)
+}
+
+/// Whether the cached download URLs of a tool's listing are its own
+fn owns_listing(tool: &Tool) -> bool {
+    tool.listing_owner().name == tool.name
 }
 
-/// Get the binary directory for the current platform
-fn get_bin_dir() -> Result<PathBuf, Box<dyn Error>> {
+/// Get a tool's binary directory for the current platform
+fn get_bin_dir(tool: &Tool) -> Result<PathBuf, Box<dyn Error>> {
     let platform = Platform::detect();
-    get_bin_dir_with_platform(&platform)
+    get_bin_dir_with_platform(tool, &platform)
 }
 
-/// Get the binary directory for a specific platform
+/// Get a tool's binary directory for a specific platform
 ///
 /// Creates the directory structure if it doesn't exist.
 ///
 /// # Arguments
+/// * `tool` - Tool to get directory for
 /// * `platform` - Platform to get directory for
 ///
 /// # Returns
 /// Path to the platform-specific binary directory
-fn get_bin_dir_with_platform(platform: &Platform) -> Result<PathBuf, Box<dyn Error>> {
+fn get_bin_dir_with_platform(tool: &Tool, platform: &Platform) -> Result<PathBuf, Box<dyn Error>> {
     let home = std::env::var("HOME")?;
-    let bin_dir = PathBuf::from(&home).join(OC_BIN_DIR).join(platform.name);
+    let bin_dir = PathBuf::from(&home)
+        .join(tool.bin_dir())
+        .join(platform.name);
     fs::create_dir_all(&bin_dir)?;
     Ok(bin_dir)
 }
 
-/// Download and extract the OpenShift client binary
+/// Download the tool's archive from the first URL that serves it and install it
+///
+/// Local mirror URLs are read from the filesystem. Connection errors, error
+/// statuses and missing files move on to the next URL. Once an
+/// archive has been downloaded it is installed by `install_archive`; a
+/// checksum mismatch is reported rather than retried elsewhere.
+///
+/// # Arguments
+/// * `bin_path` - Target path for the extracted binary
+/// * `urls` - Candidate download URLs in order of preference
+/// * `cache_name` - Name of the download in the archive cache
+/// * `opts` - Output and archive cache settings, and the tool
+///
+/// # Returns
+/// The URL the archive was downloaded from
+fn download_and_extract_from_mirrors(
+    bin_path: &Path,
+    urls: &[String],
+    cache_name: &str,
+    opts: InstallOptions,
+) -> Result<String, Box<dyn Error>> {
+    let mut errors = vec![];
+    let download_path = download_path(bin_path);
+
+    for url in urls {
+        if opts.verbose {
+            eprintln!("Downloading from: {url}");
+        }
+        match download_archive(url, &download_path) {
+            Ok(sha256) => {
+                let installed =
+                    install_archive(bin_path, url, cache_name, &download_path, &sha256, opts);
+                let _ = fs::remove_file(&download_path);
+                installed?;
+                return Ok(url.clone());
+            }
+            Err(e) => {
+                let error = format!("Failed to download: {url} ({e})");
+                if opts.verbose {
+                    eprintln!("{error}");
+                }
+                errors.push(error);
+            }
+        }
+    }
+
+    if errors.len() == 1 {
+        return Err(errors.remove(0).into());
+    }
+    Err(format!(
+        "Failed to download from any mirror:\n  {}",
+        errors.join("\n  ")
+    )
+    .into())
+}
+
+/// Path an archive is downloaded to before it is installed to `bin_path`
+fn download_path(bin_path: &Path) -> PathBuf {
+    let name = bin_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("binary");
+    bin_path.with_file_name(format!("{name}.download"))
+}
+
+/// Stream an archive from a mirror to a file, hashing it on the way
 ///
-/// Downloads the tar.gz archive from the specified URL, extracts the
-/// oc binary, and sets appropriate file permissions.
+/// A partial download is removed.
+///
+/// # Returns
+/// The lowercase hex sha256 of the archive
+fn download_archive(url: &str, dest: &Path) -> Result<String, Box<dyn Error>> {
+    let mut reader = HashingReader::new(fetch_reader(url)?);
+    let copied = fs::File::create(dest).and_then(|mut file| io::copy(&mut reader, &mut file));
+    if let Err(e) = copied {
+        let _ = fs::remove_file(dest);
+        return Err(e.into());
+    }
+    Ok(reader.finish()?)
+}
+
+/// Verify a downloaded archive and install the tool's binary from it
+///
+/// Checks the archive against the `sha256sum.txt` published next to it and
+/// installs the binary via `install_payload`. Verified archives are
+/// kept in the archive cache unless it is disabled.
 ///
 /// # Arguments
-/// * `_version` - Version being downloaded (for error messages, currently unused)
-/// * `oc_path` - Target path for the extracted binary
-/// * `download_url` - URL to download the binary from
-fn download_and_extract_with_url(
-    _version: &str,
-    oc_path: &PathBuf,
+/// * `bin_path` - Target path for the extracted binary
+/// * `download_url` - URL the archive was downloaded from
+/// * `cache_name` - Name to keep the archive under in the archive cache
+/// * `archive` - Downloaded archive
+/// * `actual` - sha256 of the downloaded archive
+/// * `opts` - Output and archive cache settings, and the tool
+fn install_archive(
+    bin_path: &Path,
     download_url: &str,
+    cache_name: &str,
+    archive: &Path,
+    actual: &str,
+    opts: InstallOptions,
 ) -> Result<(), Box<dyn Error>> {
-    let resp = reqwest::blocking::get(download_url)?;
-    if !resp.status().is_success() {
-        return Err(format!("Failed to download: {download_url}").into());
+    let name = archive_file_name(download_url);
+
+    match fetch_published_sha256(download_url) {
+        Some(expected) if expected != actual => {
+            return Err(
+                format!("sha256 mismatch for {name} (expected {expected}, got {actual})").into(),
+            );
+        }
+        Some(_) => {
+            if opts.verbose {
+                eprintln!("Verified sha256 of {name}");
+            }
+            if opts.archive_cache {
+                let stored = ArchiveCache::open_default()
+                    .and_then(|archives| archives.insert(cache_name, archive, actual));
+                if let Err(e) = stored
+                    && opts.verbose
+                {
+                    eprintln!("Warning: Failed to cache archive {name}: {e}");
+                }
+            }
+        }
+        None => {
+            // Unverified archives are installed but never cached
+            if opts.verbose {
+                eprintln!("No published checksum for {name}, skipping verification");
+            }
+        }
+    }
+
+    install_payload(opts.tool, fs::File::open(archive)?, bin_path)
+}
+
+/// Get the file name component of a download URL
+fn archive_file_name(download_url: &str) -> &str {
+    download_url.rsplit('/').next().unwrap_or(download_url)
+}
+
+/// Fetch the digest the mirror publishes for an archive
+///
+/// Looks the archive up in the `sha256sum.txt` that sits in the same
+/// directory. Returns `None` if the file is missing or doesn't list it.
+fn fetch_published_sha256(download_url: &str) -> Option<String> {
+    let (dir, name) = download_url.rsplit_once('/')?;
+    let sums = fetch_bytes(&format!("{dir}/sha256sum.txt")).ok()?;
+    find_checksum(&String::from_utf8_lossy(&sums), name)
+}
+
+/// Look up an archive in the archive cache
+///
+/// Cache failures are never fatal; they just mean a download.
+fn cached_archive(name: &str, verbose: bool) -> Option<PathBuf> {
+    match ArchiveCache::open_default().and_then(|archives| archives.get(name)) {
+        Ok(found) => found,
+        Err(e) => {
+            if verbose {
+                eprintln!("Warning: Failed to read archive cache: {e}");
+            }
+            None
+        }
+    }
+}
+
+/// Install a tool's binary from its download, packaged as the tool says
+///
+/// Tarballs are decompressed and searched for the binary and the tool's
+/// bundled binaries (e.g. kubectl next to oc), which land next to `path` as
+/// named by `staged_bundled_paths`; bare binaries are copied as they are.
+fn install_payload<R: Read>(tool: &Tool, mut reader: R, path: &Path) -> Result<(), Box<dyn Error>> {
+    match tool.packaging {
+        Packaging::TarGz => {
+            let bundled = staged_bundled_paths(tool, path);
+            extract_binary_from_tar(GzDecoder::new(reader), tool.archive_members, path, &bundled)
+        }
+        Packaging::Binary => {
+            let mut out = fs::File::create(path)?;
+            io::copy(&mut reader, &mut out)?;
+            set_executable(path)
+        }
+    }
+}
+
+/// Paths a tool's bundled binaries are installed to, next to its binary
+///
+/// A bundled `kubectl` next to `oc-4.19.0` becomes `kubectl-4.19.0`.
+fn bundled_paths(tool: &Tool, bin_path: &Path) -> Vec<(&'static str, PathBuf)> {
+    let Some(version) = bin_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| tool.version_from_file_name(name))
+    else {
+        return vec![];
+    };
+    tool.bundled
+        .iter()
+        .map(|&name| (name, bin_path.with_file_name(format!("{name}-{version}"))))
+        .collect()
+}
+
+/// Path a binary is staged at before it replaces `bin_path`
+///
+/// Hidden, so listings never mistake it for an installed version.
+fn staged_path(bin_path: &Path) -> PathBuf {
+    let name = bin_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("binary");
+    bin_path.with_file_name(format!(".{name}.part"))
+}
+
+/// Paths a tool's bundled binaries are extracted to next to a staged binary
+///
+/// A bundled `kubectl` next to `.oc-4.19.0.part` is staged as
+/// `.oc-4.19.0.part.kubectl`.
+fn staged_bundled_paths(tool: &Tool, staged_path: &Path) -> Vec<(&'static str, PathBuf)> {
+    let Some(staged_name) = staged_path.file_name().and_then(|name| name.to_str()) else {
+        return vec![];
+    };
+    tool.bundled
+        .iter()
+        .map(|&name| {
+            (
+                name,
+                staged_path.with_file_name(format!("{staged_name}.{name}")),
+            )
+        })
+        .collect()
+}
+
+/// Move a staged binary and its bundled binaries into place
+///
+/// Renames replace an installed binary of the same version in one step.
+/// A bundled binary the new download lacks is removed, so the bundled
+/// binaries always match the binary next to them.
+fn commit_staged(tool: &Tool, staged_path: &Path, bin_path: &Path) -> Result<(), Box<dyn Error>> {
+    let staged = staged_bundled_paths(tool, staged_path);
+    for ((_, from), (_, to)) in staged.iter().zip(bundled_paths(tool, bin_path)) {
+        if from.exists() {
+            fs::rename(from, &to)?;
+        } else {
+            remove_if_exists(&to)?;
+        }
+    }
+    fs::rename(staged_path, bin_path)?;
+    Ok(())
+}
+
+/// Remove a staged binary and its bundled binaries after a failed install
+fn discard_staged(tool: &Tool, staged_path: &Path) {
+    let _ = fs::remove_file(staged_path);
+    for (_, path) in staged_bundled_paths(tool, staged_path) {
+        let _ = fs::remove_file(path);
     }
-    let tar_gz = GzDecoder::new(resp);
-    let mut archive = Archive::new(tar_gz);
+}
+
+/// Extract a binary from a tar stream
+///
+/// Scans the archive for an entry named like one of `members`, writes it to
+/// `dest` and sets appropriate file permissions. When several are present,
+/// the one listed first in `members` wins. Entries named like one of
+/// `bundled` are extracted to their paths too, if the archive has them.
+/// Callers wrap the reader in a decoder when the archive is compressed.
+fn extract_binary_from_tar<R: Read>(
+    reader: R,
+    members: &[&str],
+    dest: &Path,
+    bundled: &[(&str, PathBuf)],
+) -> Result<(), Box<dyn Error>> {
+    let mut archive = Archive::new(reader);
+    let mut best = members.len();
 
     for entry in archive.entries()? {
         let mut entry = entry?;
-        let path = entry.path()?;
-        if path.ends_with("oc") {
-            let mut out = fs::File::create(oc_path)?;
+        let path = entry.path()?.into_owned();
+        let rank = members.iter().position(|member| path.ends_with(member));
+        if let Some(rank) = rank.filter(|&rank| rank < best) {
+            let mut out = fs::File::create(dest)?;
             io::copy(&mut entry, &mut out)?;
-            set_executable(oc_path)?;
-            return Ok(());
+            best = rank;
+        } else if let Some((_, bundled_path)) =
+            bundled.iter().find(|(name, _)| path.ends_with(name))
+        {
+            let mut out = fs::File::create(bundled_path)?;
+            io::copy(&mut entry, &mut out)?;
+            set_executable(bundled_path)?;
         }
     }
 
-    Err("oc binary not found in archive".into())
+    if best == members.len() {
+        let name = members.first().copied().unwrap_or("binary");
+        return Err(format!("{name} binary not found in archive").into());
+    }
+    set_executable(dest)
 }
 
 /// Set executable permissions on a file
-fn set_executable(path: &PathBuf) -> Result<(), Box<dyn Error>> {
+fn set_executable(path: &Path) -> Result<(), Box<dyn Error>> {
     use std::os::unix::fs::PermissionsExt;
     fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
     Ok(())
 }
 
-/// List all locally installed OpenShift client versions
+/// List all locally installed versions of a tool
 ///
-/// Scans the binary directory for files matching the "oc-{version}" pattern
-/// and returns a sorted list of versions.
+/// Scans the tool's binary directory for files matching the
+/// "{binary}-{version}" pattern and returns a sorted list of versions.
 ///
 /// # Returns
 /// Vector of version strings sorted by semantic version
-fn list_installed_versions() -> Result<Vec<String>, Box<dyn Error>> {
-    let bin_dir = get_bin_dir()?;
+fn list_installed_versions(tool: &Tool) -> Result<Vec<String>, Box<dyn Error>> {
+    let bin_dir = get_bin_dir(tool)?;
     let mut versions = vec![];
 
     if bin_dir.exists() {
         for entry in fs::read_dir(bin_dir)? {
             let entry = entry?;
             let fname = entry.file_name().into_string().unwrap();
-            if let Some(version) = fname.strip_prefix("oc-") {
+            if let Some(version) = tool.version_from_file_name(&fname) {
                 versions.push(version.to_string());
             }
         }
@@ -713,49 +2277,97 @@ fn list_installed_versions() -> Result<Vec<String>, Box<dyn Error>> {
     Ok(versions)
 }
 
-fn active_oc_version() -> Option<String> {
+fn active_version(tool: &Tool) -> Option<String> {
     let home = std::env::var("HOME").ok()?;
-    let symlink = PathBuf::from(&home).join(".local/bin/oc");
+    let symlink = PathBuf::from(&home).join(".local/bin").join(tool.binary);
     let target = fs::read_link(&symlink).ok()?;
     let fname = target.file_name()?.to_str()?;
-    fname.strip_prefix("oc-").map(String::from)
+    tool.version_from_file_name(fname).map(String::from)
 }
 
-/// Check if a version exists on the OpenShift mirror
+/// Check if a version exists on any configured mirror
 ///
-/// First checks the cache for the version URL, then falls back to HTTP request.
+/// Sends a HEAD request to each candidate download URL (or checks the file
+/// of a local mirror), starting with the one remembered in the cache, until
+/// one of them serves the version.
 ///
 /// # Arguments
+/// * `tool` - Tool to check
 /// * `version` - Version to check
 /// * `platform` - Platform to check for
 ///
 /// # Returns
-/// `true` if the version exists on the mirror
-fn version_exists_on_mirror(version: &str, platform: &Platform) -> Result<bool, Box<dyn Error>> {
+/// `true` if some mirror serves the version
+///
+/// # Errors
+/// Returns the last connection error if no mirror could be reached at all
+fn version_exists_on_mirror(
+    tool: &Tool,
+    version: &str,
+    platform: &Platform,
+) -> Result<bool, Box<dyn Error>> {
     let client = reqwest::blocking::Client::new();
+    let mut reached = false;
+    let mut last_error = None;
 
-    // Try to get URL from cache first
-    if let Some(cache) = load_cached_versions()?
-        && let Some(url) = cache.get_download_url(version, platform.name)
-    {
-        let resp = client.head(&url).send()?;
-        return Ok(resp.status().is_success());
+    for url in candidate_download_urls(tool, version, platform)? {
+        if let Some(path) = local_path(&url) {
+            if path.is_file() {
+                return Ok(true);
+            }
+            reached = true;
+            continue;
+        }
+        match client.head(&url).send() {
+            Ok(resp) if resp.status().is_success() => return Ok(true),
+            Ok(_) => reached = true,
+            Err(e) => last_error = Some(e),
+        }
     }
 
-    // Fallback to building URL and checking
-    let url = platform.build_download_url(version);
-    let resp = client.head(&url).send()?;
-    Ok(resp.status().is_success())
+    match last_error {
+        Some(e) if !reached => Err(e.into()),
+        _ => Ok(false),
+    }
+}
+
+/// Whether a symlink points at an upstream kubectl installed with `--kubectl`
+fn links_to_upstream_kubectl(symlink: &Path, platform: &Platform) -> bool {
+    match (
+        fs::read_link(symlink),
+        get_bin_dir_with_platform(&KUBECTL, platform),
+    ) {
+        (Ok(target), Ok(bin_dir)) => target.starts_with(bin_dir),
+        _ => false,
+    }
 }
 
-/// Set a specific version as the default OpenShift client
-fn set_default_oc_with_platform(version: &str, platform: &Platform) -> Result<(), Box<dyn Error>> {
-    let bin_dir = get_bin_dir_with_platform(platform)?;
-    let oc_path = bin_dir.join(format!("oc-{version}"));
+/// Set a specific version as the default for the selected tool
+///
+/// Points `~/.local/bin/<binary>` at the version, and each bundled binary
+/// (kubectl for oc) at its copy from the same archive. Where the archive
+/// had none, or with `--kubectl-from-oc`, the bundled name links to the
+/// tool's binary instead. A kubectl link to an upstream kubectl installed
+/// with `--kubectl` is left alone unless `--kubectl-from-oc` is given.
+///
+/// For oc, the shell completion of the new oc (and its bundled kubectl) is
+/// activated too, unless `--no-oc-completion` is given.
+fn set_default_with_platform(
+    version: &str,
+    platform: &Platform,
+    opts: InstallOptions,
+) -> Result<(), Box<dyn Error>> {
+    let tool = opts.tool;
+    let bin_dir = get_bin_dir_with_platform(tool, platform)?;
+    let bin_path = bin_dir.join(tool.file_name(version));
 
     // Ensure the binary exists (download if needed)
-    if !oc_path.exists() {
-        let (_, _, _) = ensure_oc_binary_with_platform(version, platform, false)?;
+    if !bin_path.exists() {
+        let quiet = InstallOptions {
+            verbose: false,
+            ..opts
+        };
+        let (_, _, _) = ensure_binary_with_platform(version, platform, quiet)?;
     }
 
     // Create ~/.local/bin directory and symlinks
@@ -764,16 +2376,53 @@ fn set_default_oc_with_platform(version: &str, platform: &Platform) -> Result<()
     fs::create_dir_all(&local_bin)?;
 
     // Create symlinks (binary existence is already guaranteed above)
-    let symlink_oc = local_bin.join("oc");
-    let symlink_kubectl = local_bin.join("kubectl");
+    let mut symlinks = vec![(local_bin.join(tool.binary), bin_path.clone())];
+    for (name, bundled_path) in bundled_paths(tool, &bin_path) {
+        // An upstream kubectl from --kubectl stays until explicitly replaced
+        if name == KUBECTL.binary
+            && !opts.kubectl_from_oc
+            && links_to_upstream_kubectl(&local_bin.join(name), platform)
+        {
+            if opts.verbose {
+                eprintln!("Keeping upstream kubectl (use --kubectl bundled to replace it)");
+            }
+            continue;
+        }
+        // Fall back to the tool's own binary, which oc can stand in for
+        let target = if bundled_path.exists() && !opts.kubectl_from_oc {
+            bundled_path
+        } else {
+            if opts.verbose && !opts.kubectl_from_oc {
+                eprintln!("No {name} in the archive, linking it to {}", tool.binary);
+            }
+            bin_path.clone()
+        };
+        symlinks.push((local_bin.join(name), target));
+    }
 
-    // Remove existing symlinks (including broken ones)
-    remove_if_exists(&symlink_oc)?;
-    remove_if_exists(&symlink_kubectl)?;
+    for (symlink, target) in &symlinks {
+        // Remove existing symlinks (including broken ones)
+        remove_if_exists(symlink)?;
+        create_symlink(target, symlink)?;
+    }
 
-    // Create new symlinks
-    create_symlink(&oc_path, &symlink_oc)?;
-    create_symlink(&oc_path, &symlink_kubectl)?;
+    if tool.is_oc() && opts.oc_completion {
+        // kubectl completion only comes from a real kubectl; oc would
+        // generate completion for "oc" under the kubectl name
+        for (symlink, target) in &symlinks {
+            let command = symlink.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if command != tool.binary && target == &bin_path {
+                continue;
+            }
+            // A stale completion must not fail the switch itself
+            if let Err(e) =
+                ovc::completion::activate_client_completions(command, target, version, opts.verbose)
+                && opts.verbose
+            {
+                eprintln!("Warning: failed to activate {command} completion: {e}");
+            }
+        }
+    }
 
     Ok(())
 }
@@ -806,10 +2455,10 @@ fn create_symlink(target: &Path, link: &Path) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-/// Check for existing oc binary in PATH
-/// Ignores the oc binary in ~/.local/bin since that's managed by ovc itself.
-/// # Returns: `Some(path)` if an oc binary is found in PATH (excluding ~/.local/bin), `None` otherwise
-fn check_existing_oc_in_path() -> Option<PathBuf> {
+/// Check for an existing binary of the given name in PATH
+/// Ignores the binary in ~/.local/bin since that's managed by ovc itself.
+/// # Returns: `Some(path)` if the binary is found in PATH (excluding ~/.local/bin), `None` otherwise
+fn check_existing_in_path(binary: &str) -> Option<PathBuf> {
     let path_var = std::env::var("PATH").ok()?;
     let home = std::env::var("HOME").ok()?;
     let local_bin = PathBuf::from(&home).join(".local/bin");
@@ -819,7 +2468,7 @@ fn check_existing_oc_in_path() -> Option<PathBuf> {
             continue;
         }
 
-        let candidate = Path::new(dir).join("oc");
+        let candidate = Path::new(dir).join(binary);
 
         // Skip if this is in ~/.local/bin (managed by ovc)
         if Path::new(dir) == local_bin {
@@ -839,56 +2488,3 @@ fn check_existing_oc_in_path() -> Option<PathBuf> {
 
     None
 }
-
-/// Print bash completion script
-fn print_bash_completion() {
-    print!(
-        r#"# bash completion for ovc
-
-_ovc_completions() {{
-    local cur prev
-    COMPREPLY=()
-    cur="${{COMP_WORDS[COMP_CWORD]}}"
-    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
-
-    if [[ "${{cur}}" == -* ]]; then
-        local options=(
-            "--completion    (Generate shell completion script)"
-            "-h              (Print help)"
-            "--help          (Print help)"
-            "-i              (List installed versions)"
-            "--installed     (List installed versions)"
-            "--insecure      (Skip TLS certificate verification)"
-            "-k              (Skip TLS certificate verification)"
-            "-l              (List available versions from the mirror)"
-            "--list          (List available versions from the mirror)"
-            "-m              (Download version matching connected cluster)"
-            "--match-server  (Download version matching connected cluster)"
-            "-p              (Remove all installed versions except active)"
-            "--prune         (Remove all installed versions except active)"
-            "-v              (Make the operation more talkative)"
-            "--verbose       (Make the operation more talkative)"
-            "--version       (Print version)"
-        )
-
-        local IFS=$'\n'
-        local opt name padded
-        local width=$((COLUMNS - 1))
-        for opt in "${{options[@]}}"; do
-            name="${{opt%%  *}}"
-            if [[ "$name" == "${{cur}}"* ]]; then
-                printf -v padded "%-${{width}}s" "$opt"
-                COMPREPLY+=("$padded")
-            fi
-        done
-
-        if ((${{#COMPREPLY[@]}} == 1)); then
-            COMPREPLY[0]="${{COMPREPLY[0]%%  *}}"
-        fi
-    fi
-}}
-
-complete -o nosort -F _ovc_completions ovc
-"#
-    );
-}
@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Mirror selection and latency probing
+//!
+//! ovc can be pointed at several mirrors laid out like the public OpenShift
+//! mirror, for example an internal Artifactory in front of
+//! mirror.openshift.com. They are tried in the configured order, moving on
+//! to the next one on connection errors, server errors or missing versions.
+//!
+//! A mirror can also be a `file://` URL or a plain path to a copy of the
+//! mirror tree (e.g. an rsynced subset on an NFS share). Such mirrors are
+//! listed and read straight from the filesystem.
+
+use std::error::Error;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::Platform;
+use crate::config::Config;
+use crate::tool::Tool;
+
+/// Outcome of probing one mirror
+pub struct MirrorProbe {
+    /// Mirror base URL
+    pub mirror: String,
+    /// Round-trip time of the version listing request, or why it failed
+    pub latency: Result<Duration, String>,
+}
+
+/// Get the configured mirrors in order of preference
+///
+/// # Errors
+/// Returns error if the config file exists but cannot be parsed
+pub fn configured_mirrors() -> Result<Vec<String>, Box<dyn Error>> {
+    Ok(Config::load()?.mirrors())
+}
+
+/// Get the filesystem path behind a local mirror URL
+///
+/// `file:///path`, `file://localhost/path` and plain paths are local; any
+/// other `scheme://` URL is not.
+///
+/// # Returns
+/// `Some(path)` for local URLs, `None` for remote ones
+///
+/// # Examples
+/// ```
+/// use std::path::PathBuf;
+/// use ovc::mirror::local_path;
+/// assert_eq!(local_path("file:///srv/mirror"), Some(PathBuf::from("/srv/mirror")));
+/// assert_eq!(local_path("/srv/mirror"), Some(PathBuf::from("/srv/mirror")));
+/// assert_eq!(local_path("https://mirror.openshift.com"), None);
+/// ```
+#[must_use]
+pub fn local_path(url: &str) -> Option<PathBuf> {
+    if let Some(rest) = url.strip_prefix("file://") {
+        // file://localhost/path is the long form of file:///path
+        let path = rest.strip_prefix("localhost").unwrap_or(rest);
+        return Some(PathBuf::from(path));
+    }
+    if url.contains("://") {
+        return None;
+    }
+    Some(PathBuf::from(url))
+}
+
+/// Read a file from a mirror, over HTTP or from the filesystem
+///
+/// # Arguments
+/// * `url` - URL (or path) of the file
+///
+/// # Returns
+/// The file contents
+///
+/// # Errors
+/// Returns error if the file cannot be read or the server answers with an
+/// error status
+pub fn fetch_bytes(url: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    if let Some(path) = local_path(url) {
+        return fs::read(&path).map_err(|e| format!("{} ({e})", path.display()).into());
+    }
+    reqwest::blocking::get(url)
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .and_then(reqwest::blocking::Response::bytes)
+        .map(|bytes| bytes.to_vec())
+        .map_err(|e| e.without_url().to_string().into())
+}
+
+/// Open a file on a mirror for streaming, over HTTP or from the filesystem
+///
+/// Unlike `fetch_bytes`, the contents are read as they are consumed, so
+/// large archives never have to fit in memory.
+///
+/// # Arguments
+/// * `url` - URL (or path) of the file
+///
+/// # Errors
+/// Returns error if the file cannot be opened or the server answers with an
+/// error status
+pub fn fetch_reader(url: &str) -> Result<Box<dyn Read>, Box<dyn Error>> {
+    if let Some(path) = local_path(url) {
+        let file = fs::File::open(&path).map_err(|e| format!("{} ({e})", path.display()))?;
+        return Ok(Box::new(file));
+    }
+    let resp = reqwest::blocking::get(url)
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .map_err(|e| e.without_url().to_string())?;
+    Ok(Box::new(resp))
+}
+
+/// Measure how long a mirror takes to answer a version listing request
+///
+/// Local mirrors are timed by reading their version directory.
+///
+/// # Arguments
+/// * `mirror` - Mirror base URL
+/// * `platform` - Platform whose listing is requested
+///
+/// # Errors
+/// Returns error if the mirror is unreachable or answers with an error status
+pub fn probe_mirror(mirror: &str, platform: &Platform) -> Result<Duration, Box<dyn Error>> {
+    let url = Tool::OC.build_versions_url_for(platform, mirror);
+
+    if let Some(dir) = local_path(&url) {
+        let start = Instant::now();
+        fs::read_dir(&dir).map_err(|e| format!("{} ({e})", dir.display()))?;
+        return Ok(start.elapsed());
+    }
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let start = Instant::now();
+    let resp = client.head(&url).send()?;
+    let elapsed = start.elapsed();
+
+    if !resp.status().is_success() {
+        return Err(format!("{url} ({})", resp.status()).into());
+    }
+    Ok(elapsed)
+}
+
+/// Probe every mirror in order
+#[must_use]
+pub fn probe_mirrors(mirrors: &[String], platform: &Platform) -> Vec<MirrorProbe> {
+    mirrors
+        .iter()
+        .map(|mirror| MirrorProbe {
+            mirror: mirror.clone(),
+            latency: probe_mirror(mirror, platform).map_err(|e| e.to_string()),
+        })
+        .collect()
+}
+
+/// Order mirrors fastest first, keeping unreachable ones last
+///
+/// Unreachable mirrors keep their relative order, so a temporarily down
+/// mirror doesn't lose its place among the other fallbacks.
+///
+/// # Returns
+/// Mirror base URLs in their new order
+#[must_use]
+pub fn order_by_latency(probes: &[MirrorProbe]) -> Vec<String> {
+    let mut ordered: Vec<&MirrorProbe> = probes.iter().collect();
+    ordered.sort_by_key(|p| match p.latency {
+        Ok(latency) => (false, latency),
+        Err(_) => (true, Duration::ZERO),
+    });
+    ordered.iter().map(|p| p.mirror.clone()).collect()
+}
@@ -0,0 +1,759 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Extracting oc from OpenShift release images
+//!
+//! Some builds (hotfixes, nightlies, private releases) only exist as release
+//! images in a container registry and never as mirror tarballs. A release
+//! image carries `release-manifests/image-references`, an `ImageStream`
+//! naming the component images of the release by digest. The `cli` image
+//! ships `oc` as `/usr/bin/oc`; `cli-artifacts` ships per-platform builds
+//! under `/usr/share/openshift/`.
+//!
+//! Images are read from a registry speaking the OCI distribution API, or
+//! from an on-disk OCI image layout (`oci:<dir>[:<tag>]`). Layers are
+//! fetched from the top of the image down, stopping at the first layer that
+//! contains the wanted file, and every blob is checked against its digest.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use flate2::read::GzDecoder;
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{ACCEPT, AUTHORIZATION, WWW_AUTHENTICATE};
+use serde_json::Value;
+use tar::Archive;
+
+use crate::Platform;
+use crate::checksum::{HashingReader, sha256_hex};
+
+/// Manifest media types accepted from registries
+const MANIFEST_MEDIA_TYPES: &str = "application/vnd.oci.image.index.v1+json, \
+     application/vnd.docker.distribution.manifest.list.v2+json, \
+     application/vnd.oci.image.manifest.v1+json, \
+     application/vnd.docker.distribution.manifest.v2+json";
+
+/// Path of the component image list inside a release image
+const IMAGE_REFERENCES: &str = "release-manifests/image-references";
+
+/// Prefix of image references that point at an on-disk OCI layout
+const OCI_LAYOUT_PREFIX: &str = "oci:";
+
+/// A parsed container image reference such as
+/// `quay.io/openshift-release-dev/ocp-release:4.16.3-x86_64`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageReference {
+    /// Registry host, with port if any (e.g. `quay.io`)
+    pub registry: String,
+    /// Repository path (e.g. `openshift-release-dev/ocp-release`)
+    pub repository: String,
+    /// Tag or `sha256:` digest
+    pub reference: String,
+}
+
+impl ImageReference {
+    /// Parse an image reference
+    ///
+    /// References without a registry default to Docker Hub, and references
+    /// without a tag or digest to `latest`, as with `podman pull`.
+    ///
+    /// # Errors
+    /// Returns error if the reference has no repository
+    ///
+    /// # Examples
+    /// ```
+    /// use ovc::oci::ImageReference;
+    /// let image = ImageReference::parse("quay.io/openshift-release-dev/ocp-release:4.16.3-x86_64").unwrap();
+    /// assert_eq!(image.registry, "quay.io");
+    /// assert_eq!(image.repository, "openshift-release-dev/ocp-release");
+    /// assert_eq!(image.reference, "4.16.3-x86_64");
+    /// ```
+    pub fn parse(image: &str) -> Result<Self, Box<dyn Error>> {
+        let (name, reference) = if let Some((name, digest)) = image.split_once('@') {
+            (name, digest.to_string())
+        } else {
+            // A colon after the last slash separates the tag; earlier ones are ports
+            let last_slash = image.rfind('/').map_or(0, |i| i + 1);
+            match image[last_slash..].rfind(':') {
+                Some(i) => (
+                    &image[..last_slash + i],
+                    image[last_slash + i + 1..].to_string(),
+                ),
+                None => (image, "latest".to_string()),
+            }
+        };
+
+        let (registry, repository) = match name.split_once('/') {
+            Some((host, rest))
+                if host.contains('.') || host.contains(':') || host == "localhost" =>
+            {
+                (host.to_string(), rest.to_string())
+            }
+            Some(_) => ("docker.io".to_string(), name.to_string()),
+            None => ("docker.io".to_string(), format!("library/{name}")),
+        };
+
+        if repository.is_empty() || reference.is_empty() {
+            return Err(format!("Invalid image reference: {image}").into());
+        }
+
+        Ok(Self {
+            registry,
+            repository,
+            reference,
+        })
+    }
+
+    /// The same repository, pinned to a digest
+    #[must_use]
+    pub fn with_digest(&self, digest: &str) -> Self {
+        Self {
+            reference: digest.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Whether the reference pins a digest rather than a tag
+    #[must_use]
+    pub fn is_digest(&self) -> bool {
+        self.reference.starts_with("sha256:")
+    }
+
+    /// Base URL of the registry's distribution API
+    fn api_base(&self) -> String {
+        let host = if self.registry == "docker.io" {
+            "registry-1.docker.io"
+        } else {
+            &self.registry
+        };
+        // Local test and development registries rarely have certificates
+        let scheme = if host.starts_with("localhost") || host.starts_with("127.0.0.1") {
+            "http"
+        } else {
+            "https"
+        };
+        format!("{scheme}://{host}/v2/{}", self.repository)
+    }
+}
+
+// =============================================================================
+// Registry credentials
+// =============================================================================
+
+/// Registry credentials from a Docker `config.json` or an OpenShift pull secret
+///
+/// Both use the same format:
+/// `{"auths": {"quay.io": {"auth": "<base64 user:password>"}}}`.
+#[derive(Default)]
+pub struct RegistryAuth {
+    /// Credentials keyed by registry host, optionally with a repository path
+    auths: HashMap<String, (String, String)>,
+}
+
+impl RegistryAuth {
+    /// Load credentials from a file, or from the default locations
+    ///
+    /// Without an explicit file, the first of `$REGISTRY_AUTH_FILE`,
+    /// `$XDG_RUNTIME_DIR/containers/auth.json` and `~/.docker/config.json`
+    /// that exists is used. No file at all means anonymous access.
+    ///
+    /// # Arguments
+    /// * `path` - Auth file to use instead of the default locations
+    ///
+    /// # Errors
+    /// Returns error if the auth file cannot be read or parsed
+    pub fn load(path: Option<&Path>) -> Result<Self, Box<dyn Error>> {
+        let path = match path {
+            Some(path) => Some(path.to_path_buf()),
+            None => default_auth_files().into_iter().find(|p| p.is_file()),
+        };
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        Self::from_json(&content)
+            .map_err(|e| format!("Invalid registry auth file {}: {e}", path.display()).into())
+    }
+
+    /// Parse credentials from the contents of an auth file
+    ///
+    /// # Errors
+    /// Returns error if the contents are not valid JSON or an `auth` entry
+    /// is not valid base64 `user:password`
+    pub fn from_json(content: &str) -> Result<Self, Box<dyn Error>> {
+        let value: Value = serde_json::from_str(content)?;
+        let mut auths = HashMap::new();
+
+        if let Some(entries) = value.get("auths").and_then(Value::as_object) {
+            for (key, entry) in entries {
+                let Some(encoded) = entry.get("auth").and_then(Value::as_str) else {
+                    continue;
+                };
+                let decoded = String::from_utf8(BASE64.decode(encoded)?)?;
+                let (user, password) = decoded
+                    .split_once(':')
+                    .ok_or_else(|| format!("auth for {key} is not user:password"))?;
+                auths.insert(
+                    normalize_auth_key(key),
+                    (user.to_string(), password.to_string()),
+                );
+            }
+        }
+
+        Ok(Self { auths })
+    }
+
+    /// Find the credentials for an image
+    ///
+    /// Entries scoped to a repository path win over registry-wide ones.
+    #[must_use]
+    pub fn credentials(&self, image: &ImageReference) -> Option<(&str, &str)> {
+        let full = format!("{}/{}", image.registry, image.repository);
+        self.auths
+            .iter()
+            .filter(|(key, _)| {
+                **key == image.registry || full == **key || full.starts_with(&format!("{key}/"))
+            })
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, (user, password))| (user.as_str(), password.as_str()))
+    }
+}
+
+/// Auth files consulted when none is given, in order of preference
+fn default_auth_files() -> Vec<PathBuf> {
+    let mut files = vec![];
+    if let Ok(file) = std::env::var("REGISTRY_AUTH_FILE") {
+        files.push(PathBuf::from(file));
+    }
+    if let Ok(runtime) = std::env::var("XDG_RUNTIME_DIR") {
+        files.push(PathBuf::from(runtime).join("containers/auth.json"));
+    }
+    if let Ok(home) = std::env::var("HOME") {
+        files.push(PathBuf::from(home).join(".docker/config.json"));
+    }
+    files
+}
+
+/// Reduce an auth file key to `host[/path]`
+///
+/// Docker writes Docker Hub as `https://index.docker.io/v1/`.
+fn normalize_auth_key(key: &str) -> String {
+    let key = key
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/');
+    let key = key.strip_suffix("/v1").unwrap_or(key);
+    match key {
+        "index.docker.io" | "registry-1.docker.io" => "docker.io".to_string(),
+        _ => key.to_string(),
+    }
+}
+
+// =============================================================================
+// Image stores
+// =============================================================================
+
+/// Where manifests and blobs are read from
+enum ImageStore<'a> {
+    /// A registry speaking the OCI distribution API
+    Registry {
+        client: Client,
+        auth: &'a RegistryAuth,
+        /// `Authorization` header values per `registry/repository`
+        tokens: HashMap<String, String>,
+    },
+    /// An OCI image layout directory
+    Layout(PathBuf),
+}
+
+impl ImageStore<'_> {
+    /// Fetch a manifest, verified against its digest when pinned by one
+    fn manifest(&mut self, image: &ImageReference) -> Result<Vec<u8>, Box<dyn Error>> {
+        let bytes = match self {
+            ImageStore::Registry { .. } => {
+                let url = format!("{}/manifests/{}", image.api_base(), image.reference);
+                let mut resp = self.get(image, &url, Some(MANIFEST_MEDIA_TYPES))?;
+                let mut bytes = vec![];
+                resp.read_to_end(&mut bytes)?;
+                bytes
+            }
+            ImageStore::Layout(dir) => {
+                if image.is_digest() {
+                    fs::read(layout_blob_path(dir, &image.reference)?)?
+                } else {
+                    let digest = layout_tag_digest(dir, &image.reference)?;
+                    let bytes = fs::read(layout_blob_path(dir, &digest)?)?;
+                    verify_digest(&bytes, &digest)?;
+                    bytes
+                }
+            }
+        };
+        if image.is_digest() {
+            verify_digest(&bytes, &image.reference)?;
+        }
+        Ok(bytes)
+    }
+
+    /// Open a blob for streaming
+    fn blob(
+        &mut self,
+        image: &ImageReference,
+        digest: &str,
+    ) -> Result<Box<dyn Read>, Box<dyn Error>> {
+        match self {
+            ImageStore::Registry { .. } => {
+                let url = format!("{}/blobs/{digest}", image.api_base());
+                Ok(Box::new(self.get(image, &url, None)?))
+            }
+            ImageStore::Layout(dir) => {
+                Ok(Box::new(fs::File::open(layout_blob_path(dir, digest)?)?))
+            }
+        }
+    }
+
+    /// GET from a registry, authenticating when challenged
+    fn get(
+        &mut self,
+        image: &ImageReference,
+        url: &str,
+        accept: Option<&str>,
+    ) -> Result<Response, Box<dyn Error>> {
+        let ImageStore::Registry {
+            client,
+            auth,
+            tokens,
+        } = self
+        else {
+            return Err("not a registry".into());
+        };
+        let scope = format!("{}/{}", image.registry, image.repository);
+
+        let send = |authorization: Option<&String>| {
+            let mut request = client.get(url);
+            if let Some(accept) = accept {
+                request = request.header(ACCEPT, accept);
+            }
+            if let Some(authorization) = authorization {
+                request = request.header(AUTHORIZATION, authorization);
+            }
+            request.send()
+        };
+
+        let mut resp = send(tokens.get(&scope))?;
+        if resp.status() == StatusCode::UNAUTHORIZED {
+            let challenge = resp
+                .headers()
+                .get(WWW_AUTHENTICATE)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            let authorization = authorize(client, auth, image, &challenge)?;
+            resp = send(Some(&authorization))?;
+            tokens.insert(scope, authorization);
+        }
+
+        if !resp.status().is_success() {
+            return Err(format!("Failed to fetch {url} ({})", resp.status()).into());
+        }
+        Ok(resp)
+    }
+}
+
+/// Answer a registry's `WWW-Authenticate` challenge
+///
+/// # Returns
+/// The `Authorization` header value to retry with
+fn authorize(
+    client: &Client,
+    auth: &RegistryAuth,
+    image: &ImageReference,
+    challenge: &str,
+) -> Result<String, Box<dyn Error>> {
+    let credentials = auth.credentials(image);
+    let (scheme, params) = parse_challenge(challenge);
+
+    if scheme.eq_ignore_ascii_case("basic") {
+        let Some((user, password)) = credentials else {
+            return Err(format!("{} requires credentials", image.registry).into());
+        };
+        return Ok(format!(
+            "Basic {}",
+            BASE64.encode(format!("{user}:{password}"))
+        ));
+    }
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return Err(format!("Unsupported registry authentication: {challenge}").into());
+    }
+
+    let realm = params
+        .get("realm")
+        .ok_or_else(|| format!("Registry challenge without realm: {challenge}"))?;
+    let scope = params
+        .get("scope")
+        .cloned()
+        .unwrap_or_else(|| format!("repository:{}:pull", image.repository));
+    let mut query = vec![("scope", scope)];
+    if let Some(service) = params.get("service") {
+        query.push(("service", service.clone()));
+    }
+
+    let url = reqwest::Url::parse_with_params(realm, &query)?;
+    let mut request = client.get(url);
+    if let Some((user, password)) = credentials {
+        request = request.basic_auth(user, Some(password));
+    }
+    let resp = request.send()?;
+    if !resp.status().is_success() {
+        return Err(format!(
+            "Registry token request for {} failed ({})",
+            image.registry,
+            resp.status()
+        )
+        .into());
+    }
+
+    let body: Value = serde_json::from_str(&resp.text()?)?;
+    let token = body
+        .get("token")
+        .or_else(|| body.get("access_token"))
+        .and_then(Value::as_str)
+        .ok_or("Registry token response without a token")?;
+    Ok(format!("Bearer {token}"))
+}
+
+/// Split a `WWW-Authenticate` header into its scheme and parameters
+///
+/// # Examples
+/// ```
+/// use ovc::oci::parse_challenge;
+/// let (scheme, params) =
+///     parse_challenge(r#"Bearer realm="https://quay.io/v2/auth",service="quay.io""#);
+/// assert_eq!(scheme, "Bearer");
+/// assert_eq!(params["realm"], "https://quay.io/v2/auth");
+/// assert_eq!(params["service"], "quay.io");
+/// ```
+#[must_use]
+pub fn parse_challenge(challenge: &str) -> (String, HashMap<String, String>) {
+    let challenge = challenge.trim();
+    let (scheme, mut rest) = challenge.split_once(' ').unwrap_or((challenge, ""));
+    let mut params = HashMap::new();
+
+    while let Some((key, after)) = rest.split_once('=') {
+        let key = key
+            .trim()
+            .trim_start_matches(',')
+            .trim()
+            .to_ascii_lowercase();
+        let after = after.trim_start();
+        let (value, remainder) = if let Some(quoted) = after.strip_prefix('"') {
+            quoted.split_once('"').unwrap_or((quoted, ""))
+        } else {
+            after.split_once(',').unwrap_or((after, ""))
+        };
+        params.insert(key, value.to_string());
+        rest = remainder;
+    }
+
+    (scheme.to_string(), params)
+}
+
+/// Path of a blob in an OCI layout
+fn layout_blob_path(dir: &Path, digest: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let hex = digest
+        .strip_prefix("sha256:")
+        .filter(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| format!("Unsupported digest: {digest}"))?;
+    Ok(dir.join("blobs/sha256").join(hex))
+}
+
+/// Find the manifest digest of a tag in an OCI layout's `index.json`
+///
+/// An empty tag selects the only manifest of a single-image layout.
+fn layout_tag_digest(dir: &Path, tag: &str) -> Result<String, Box<dyn Error>> {
+    let index_path = dir.join("index.json");
+    let index: Value = serde_json::from_str(
+        &fs::read_to_string(&index_path)
+            .map_err(|e| format!("Not an OCI layout: {} ({e})", dir.display()))?,
+    )?;
+    let manifests = index
+        .get("manifests")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let found = if tag.is_empty() {
+        match manifests {
+            [only] => Some(only),
+            _ => {
+                return Err(format!(
+                    "{} holds several images; add :<tag> to pick one",
+                    dir.display()
+                )
+                .into());
+            }
+        }
+    } else {
+        manifests.iter().find(|m| {
+            m.pointer("/annotations/org.opencontainers.image.ref.name")
+                .and_then(Value::as_str)
+                == Some(tag)
+        })
+    };
+
+    found
+        .and_then(|m| m.get("digest"))
+        .and_then(Value::as_str)
+        .map(String::from)
+        .ok_or_else(|| format!("Tag {tag} not found in {}", dir.display()).into())
+}
+
+/// Check that content matches a `sha256:` digest
+fn verify_digest(bytes: &[u8], digest: &str) -> Result<(), Box<dyn Error>> {
+    let actual = format!("sha256:{}", sha256_hex(bytes));
+    if actual != digest {
+        return Err(format!("Digest mismatch (expected {digest}, got {actual})").into());
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Release image extraction
+// =============================================================================
+
+/// Fetch the layer list of an image, resolving multi-arch indexes
+///
+/// # Returns
+/// Layer `(digest, media type)` pairs, bottom layer first
+fn image_layers(
+    store: &mut ImageStore,
+    image: &ImageReference,
+    platform: &Platform,
+) -> Result<Vec<(String, String)>, Box<dyn Error>> {
+    let manifest: Value = serde_json::from_slice(&store.manifest(image)?)?;
+
+    if let Some(manifests) = manifest.get("manifests").and_then(Value::as_array) {
+        let arch = platform.go_arch();
+        let digest = manifests
+            .iter()
+            .find(|m| {
+                m.pointer("/platform/os").and_then(Value::as_str) == Some("linux")
+                    && m.pointer("/platform/architecture").and_then(Value::as_str) == Some(arch)
+            })
+            .and_then(|m| m.get("digest"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("No linux/{arch} image in {}", image.reference))?;
+        return image_layers(store, &image.with_digest(digest), platform);
+    }
+
+    let layers = manifest
+        .get("layers")
+        .and_then(Value::as_array)
+        .ok_or("Image manifest has no layers")?;
+    layers
+        .iter()
+        .map(|layer| {
+            let digest = layer.get("digest").and_then(Value::as_str);
+            let media_type = layer
+                .get("mediaType")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            digest
+                .map(|d| (d.to_string(), media_type.to_string()))
+                .ok_or_else(|| "Image layer without a digest".into())
+        })
+        .collect()
+}
+
+/// Normalize a tar entry path to `usr/bin/oc` form
+fn entry_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    path.trim_start_matches("./")
+        .trim_start_matches('/')
+        .to_string()
+}
+
+/// Search an image's layers, top down, for a file and hand it to `found`
+///
+/// Each searched layer is read in full so its digest can be verified
+/// before the result is returned.
+///
+/// # Returns
+/// `Ok(true)` once the file was found and its layer verified
+fn find_in_layers<F>(
+    store: &mut ImageStore,
+    image: &ImageReference,
+    platform: &Platform,
+    wanted: &str,
+    mut found: F,
+) -> Result<bool, Box<dyn Error>>
+where
+    F: FnMut(&mut dyn Read) -> Result<(), Box<dyn Error>>,
+{
+    let whiteout = match wanted.rsplit_once('/') {
+        Some((dir, name)) => format!("{dir}/.wh.{name}"),
+        None => format!(".wh.{wanted}"),
+    };
+
+    for (digest, media_type) in image_layers(store, image, platform)?.into_iter().rev() {
+        if media_type.contains("zstd") {
+            return Err(format!("zstd-compressed layers are not supported: {digest}").into());
+        }
+
+        let mut hashing = HashingReader::new(store.blob(image, &digest)?);
+        let mut matched = false;
+        let mut removed = false;
+        {
+            let decoded: Box<dyn Read + '_> =
+                if media_type.contains("gzip") || media_type.is_empty() {
+                    Box::new(GzDecoder::new(&mut hashing))
+                } else {
+                    Box::new(&mut hashing)
+                };
+            let mut archive = Archive::new(decoded);
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                let path = entry_path(&entry.path()?);
+                if path == wanted {
+                    found(&mut entry)?;
+                    matched = true;
+                    break;
+                }
+                if path == whiteout {
+                    removed = true;
+                    break;
+                }
+            }
+        }
+
+        let actual = format!("sha256:{}", hashing.finish()?);
+        if actual != digest {
+            return Err(format!("Layer digest mismatch (expected {digest}, got {actual})").into());
+        }
+        if matched {
+            return Ok(true);
+        }
+        if removed {
+            return Ok(false);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Find the component image providing oc in a release image
+///
+/// # Returns
+/// The component image and the path of oc inside it
+fn release_cli_image(
+    store: &mut ImageStore,
+    release: &ImageReference,
+    platform: &Platform,
+    verbose: bool,
+) -> Result<(ImageReference, String), Box<dyn Error>> {
+    let mut references = String::new();
+    let found = find_in_layers(store, release, platform, IMAGE_REFERENCES, |reader| {
+        reader.read_to_string(&mut references)?;
+        Ok(())
+    })?;
+    if !found {
+        return Err(format!(
+            "{} is not an OpenShift release image (no {IMAGE_REFERENCES})",
+            release.reference
+        )
+        .into());
+    }
+
+    let stream: Value = serde_json::from_str(&references)?;
+    let tags = stream
+        .pointer("/spec/tags")
+        .and_then(Value::as_array)
+        .ok_or("image-references has no tags")?;
+    let component = |name: &str| {
+        tags.iter()
+            .find(|tag| tag.get("name").and_then(Value::as_str) == Some(name))
+            .and_then(|tag| tag.pointer("/from/name"))
+            .and_then(Value::as_str)
+    };
+
+    let (tag, pullspec, oc_path) = if let Some(pullspec) = component("cli") {
+        ("cli", pullspec, "usr/bin/oc".to_string())
+    } else if let Some(pullspec) = component("cli-artifacts") {
+        let path = format!("usr/share/openshift/linux_{}/oc", platform.go_arch());
+        ("cli-artifacts", pullspec, path)
+    } else {
+        return Err("Release image has no cli or cli-artifacts component".into());
+    };
+
+    if verbose {
+        if let Some(name) = stream.pointer("/metadata/name").and_then(Value::as_str) {
+            eprintln!("Release: {name}");
+        }
+        eprintln!("Component {tag}: {pullspec}");
+    }
+
+    Ok((ImageReference::parse(pullspec)?, oc_path))
+}
+
+/// Extract oc from an OpenShift release image
+///
+/// # Arguments
+/// * `image` - Release image pull spec, or `oci:<dir>[:<tag>]` for an OCI layout
+/// * `platform` - Platform whose oc build is wanted
+/// * `auth` - Registry credentials
+/// * `dest` - Path to write the oc binary to
+/// * `verbose` - Whether to show progress information
+///
+/// # Errors
+/// Returns error if an image cannot be fetched or verified, or oc is not
+/// found in the release
+pub fn extract_oc_from_release_image(
+    image: &str,
+    platform: &Platform,
+    auth: &RegistryAuth,
+    dest: &Path,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let (mut store, release) = if let Some(layout) = image.strip_prefix(OCI_LAYOUT_PREFIX) {
+        // The tag follows the last colon, unless that colon is part of the path
+        let (dir, tag) = match layout.rsplit_once(':') {
+            Some((dir, tag)) if !tag.contains('/') => (dir, tag),
+            _ => (layout, ""),
+        };
+        let release = ImageReference {
+            registry: String::new(),
+            repository: dir.to_string(),
+            reference: tag.to_string(),
+        };
+        (ImageStore::Layout(PathBuf::from(dir)), release)
+    } else {
+        let store = ImageStore::Registry {
+            client: Client::new(),
+            auth,
+            tokens: HashMap::new(),
+        };
+        (store, ImageReference::parse(image)?)
+    };
+
+    let (cli, oc_path) = release_cli_image(&mut store, &release, platform, verbose)?;
+
+    let found = find_in_layers(&mut store, &cli, platform, &oc_path, |reader| {
+        let mut out = fs::File::create(dest)?;
+        io::copy(reader, &mut out)?;
+        Ok(())
+    });
+    match found {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(format!("/{oc_path} not found in {}", cli.reference).into()),
+        Err(e) => {
+            // The file may have been written before its layer failed verification
+            let _ = fs::remove_file(dest);
+            Err(e)
+        }
+    }
+}
@@ -5,6 +5,13 @@
 //! building appropriate download URLs for OpenShift client binaries from the
 //! official mirror.
 
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::tool::Tool;
+
 /// Base URL for the OpenShift mirror where client binaries are hosted
 pub const OC_MIRROR_BASE: &str = "https://mirror.openshift.com/pub/openshift-v4";
 
@@ -25,6 +32,8 @@ pub struct Platform {
     pub binary_suffix: &'static str,
     /// File extension for the downloaded archive
     pub file_extension: &'static str,
+    /// ELF machine type (`e_machine`) of binaries that run on this platform
+    pub elf_machine: u16,
 }
 
 impl Platform {
@@ -34,19 +43,125 @@ impl Platform {
         mirror_path: "x86_64",
         binary_suffix: "linux",
         file_extension: "tar.gz",
+        elf_machine: 62,
+    };
+
+    /// Linux arm64 platform configuration
+    pub const LINUX_AARCH64: Platform = Platform {
+        name: "linux-aarch64",
+        mirror_path: "aarch64",
+        binary_suffix: "linux",
+        file_extension: "tar.gz",
+        elf_machine: 183,
     };
 
+    /// Linux ppc64le platform configuration
+    pub const LINUX_PPC64LE: Platform = Platform {
+        name: "linux-ppc64le",
+        mirror_path: "ppc64le",
+        binary_suffix: "linux",
+        file_extension: "tar.gz",
+        elf_machine: 21,
+    };
+
+    /// Linux s390x platform configuration
+    pub const LINUX_S390X: Platform = Platform {
+        name: "linux-s390x",
+        mirror_path: "s390x",
+        binary_suffix: "linux",
+        file_extension: "tar.gz",
+        elf_machine: 22,
+    };
+
+    /// Every supported platform
+    pub const ALL: [Platform; 4] = [
+        Self::LINUX_X86_64,
+        Self::LINUX_AARCH64,
+        Self::LINUX_PPC64LE,
+        Self::LINUX_S390X,
+    ];
+
     /// Automatically detect the current platform based on OS and architecture
     ///
-    /// Returns the appropriate Platform constant based on the runtime environment.
-    /// Currently only supports Linux x86_64.
+    /// Returns the appropriate Platform constant based on the runtime environment,
+    /// falling back to Linux x86_64 on unsupported architectures.
     #[must_use]
     pub fn detect() -> Platform {
-        Self::LINUX_X86_64
+        Self::from_arch(std::env::consts::ARCH).unwrap_or(Self::LINUX_X86_64)
+    }
+
+    /// Get the platform for a Rust architecture name
+    ///
+    /// # Arguments
+    /// * `arch` - Architecture as in `std::env::consts::ARCH` (e.g. "aarch64")
+    ///
+    /// # Returns
+    /// The platform, or `None` if OpenShift has no clients for it
+    ///
+    /// # Examples
+    /// ```
+    /// use ovc::Platform;
+    /// assert_eq!(Platform::from_arch("aarch64").unwrap().name, "linux-aarch64");
+    /// assert!(Platform::from_arch("riscv64").is_none());
+    /// ```
+    #[must_use]
+    pub fn from_arch(arch: &str) -> Option<Platform> {
+        // Rust calls little-endian 64-bit POWER "powerpc64" too
+        let mirror_path = match arch {
+            "powerpc64" => "ppc64le",
+            other => other,
+        };
+        Self::ALL.into_iter().find(|p| p.mirror_path == mirror_path)
+    }
+
+    /// Check that a downloaded binary is built for this platform
+    ///
+    /// Files that aren't ELF binaries (e.g. scripts) are accepted.
+    ///
+    /// # Arguments
+    /// * `path` - Binary to check
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be read, or is an ELF binary for
+    /// another machine type
+    pub fn check_binary(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut header = Vec::with_capacity(20);
+        File::open(path)?.take(20).read_to_end(&mut header)?;
+
+        match elf_machine(&header) {
+            Some(machine) if machine != self.elf_machine => {
+                let built_for = Self::ALL
+                    .iter()
+                    .find(|p| p.elf_machine == machine)
+                    .map_or_else(|| format!("ELF machine {machine}"), |p| p.name.to_string());
+                Err(format!(
+                    "{} is built for {built_for}, not {}",
+                    path.display(),
+                    self.name
+                )
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Architecture name in Go/OCI terms (e.g. "amd64" for x86_64)
+    ///
+    /// Used by image indexes and by tools whose file names follow Go's
+    /// `GOARCH` naming.
+    #[must_use]
+    pub fn go_arch(&self) -> &'static str {
+        match self.mirror_path {
+            "x86_64" => "amd64",
+            "aarch64" => "arm64",
+            other => other,
+        }
     }
 
     /// Build the download URL for a specific version on this platform
     ///
+    /// The URL layout is `Tool::OC`'s, on the public mirror.
+    ///
     /// # Arguments
     /// * `version` - The OpenShift version to download (e.g. "4.19.0")
     ///
@@ -54,15 +169,7 @@ impl Platform {
     /// Complete URL to download the specified version for this platform
     #[must_use]
     pub fn build_download_url(&self, version: &str) -> String {
-        format!(
-            "{}/{}/clients/ocp/{}/openshift-client-{}-{}.{}",
-            OC_MIRROR_BASE,
-            self.mirror_path,
-            version,
-            self.binary_suffix,
-            version,
-            self.file_extension
-        )
+        Tool::OC.build_download_url_for(self, OC_MIRROR_BASE, version)
     }
 
     /// Build the base URL for listing available versions on this platform
@@ -71,6 +178,34 @@ impl Platform {
     /// URL to the directory listing of available versions for this platform
     #[must_use]
     pub fn build_versions_url(&self) -> String {
-        format!("{}/{}/clients/ocp/", OC_MIRROR_BASE, self.mirror_path)
+        Tool::OC.build_versions_url_for(self, OC_MIRROR_BASE)
+    }
+}
+
+/// Read the machine type (`e_machine`) from an ELF header
+///
+/// # Returns
+/// The machine type, or `None` if the bytes don't start an ELF file
+///
+/// # Examples
+/// ```
+/// use ovc::platform::elf_machine;
+/// let mut header = b"\x7fELF\x02\x01".to_vec();
+/// header.resize(18, 0);
+/// header.extend_from_slice(&183u16.to_le_bytes());
+/// assert_eq!(elf_machine(&header), Some(183));
+/// assert_eq!(elf_machine(b"#!/bin/sh\n"), None);
+/// ```
+#[must_use]
+pub fn elf_machine(header: &[u8]) -> Option<u16> {
+    if header.len() < 20 || !header.starts_with(b"\x7fELF") {
+        return None;
+    }
+    let bytes = [header[18], header[19]];
+    // EI_DATA: 1 for little-endian, 2 for big-endian (s390x)
+    match header[5] {
+        1 => Some(u16::from_le_bytes(bytes)),
+        2 => Some(u16::from_be_bytes(bytes)),
+        _ => None,
     }
 }
@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Where installed binaries came from
+//!
+//! Each bin directory keeps a `manifest.json` mapping binary file names to
+//! their source and origin:
+//!
+//! ```json
+//! {
+//!   "oc-4.16.3": {
+//!     "source": "cluster",
+//!     "origin": "https://api.example.com:6443"
+//!   }
+//! }
+//! ```
+//!
+//! Binaries installed before the manifest existed have no entry. A missing
+//! or unreadable manifest is treated as empty, so provenance never stands in
+//! the way of using a binary.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// File name of the manifest inside a bin directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// How a binary was obtained
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Source {
+    /// Downloaded from a mirror
+    Mirror,
+    /// Downloaded from a cluster's console
+    Cluster,
+    /// Imported from a local file
+    Import,
+    /// Extracted from a release image
+    ReleaseImage,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Mirror => "mirror",
+            Self::Cluster => "cluster",
+            Self::Import => "import",
+            Self::ReleaseImage => "release-image",
+        })
+    }
+}
+
+/// Where one installed binary came from
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Provenance {
+    /// How it was obtained
+    pub source: Source,
+    /// Download URL, API server, file path or image it came from
+    pub origin: String,
+}
+
+/// Get the manifest path of a bin directory
+#[must_use]
+pub fn get_manifest_path(bin_dir: &Path) -> PathBuf {
+    bin_dir.join(MANIFEST_FILE)
+}
+
+/// Load a bin directory's manifest
+///
+/// # Returns
+/// Provenance by binary file name; empty if the manifest is missing or
+/// unreadable
+#[must_use]
+pub fn load_manifest(bin_dir: &Path) -> BTreeMap<String, Provenance> {
+    fs::read_to_string(get_manifest_path(bin_dir))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Look up where a binary came from
+///
+/// # Arguments
+/// * `bin_dir` - Bin directory holding the binary
+/// * `file_name` - Binary file name (e.g. "oc-4.16.3")
+#[must_use]
+pub fn lookup(bin_dir: &Path, file_name: &str) -> Option<Provenance> {
+    load_manifest(bin_dir).remove(file_name)
+}
+
+/// Record where a binary came from, replacing any earlier entry
+///
+/// # Errors
+/// Returns error if the manifest cannot be written
+pub fn record(
+    bin_dir: &Path,
+    file_name: &str,
+    provenance: Provenance,
+) -> Result<(), Box<dyn Error>> {
+    let mut manifest = load_manifest(bin_dir);
+    manifest.insert(file_name.to_string(), provenance);
+    save_manifest(bin_dir, &manifest)
+}
+
+/// Drop the entries of removed binaries
+///
+/// # Errors
+/// Returns error if the manifest cannot be written
+pub fn forget(bin_dir: &Path, file_names: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut manifest = load_manifest(bin_dir);
+    let before = manifest.len();
+    manifest.retain(|name, _| !file_names.contains(name));
+    if manifest.len() == before {
+        return Ok(());
+    }
+    save_manifest(bin_dir, &manifest)
+}
+
+/// Write a bin directory's manifest
+fn save_manifest(
+    bin_dir: &Path,
+    manifest: &BTreeMap<String, Provenance>,
+) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(bin_dir)?;
+    fs::write(
+        get_manifest_path(bin_dir),
+        serde_json::to_string_pretty(manifest)? + "\n",
+    )?;
+    Ok(())
+}
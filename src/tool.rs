@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//! Descriptors for the client tools ovc manages
+//!
+//! Besides `oc`, the mirror publishes companion tools under `clients/`:
+//! `openshift-install`, `oc-mirror` and `opm` next to the oc tarballs in
+//! `clients/ocp/<version>/`, and `butane` and `helm` in their own
+//! `clients/<tool>/` trees. Each tool is installed the same way as oc:
+//! versioned binaries in `~/.local/bin/<tool>_bins/<platform>/` and a
+//! `~/.local/bin/<tool>` symlink to the active one.
+
+use crate::Platform;
+
+/// How a tool's download is packaged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Packaging {
+    /// A gzip-compressed tarball containing the binary
+    TarGz,
+    /// The bare binary
+    Binary,
+}
+
+/// A client tool published on the mirror
+///
+/// Path templates are relative to the mirror base and may use
+/// `{mirror_path}` (e.g. `x86_64`), `{os}` (e.g. `linux`), `{arch}`
+/// (e.g. `amd64`) and, for downloads, `{version}`.
+#[derive(Debug)]
+pub struct Tool {
+    /// Tool name as given to `--tool`
+    pub name: &'static str,
+    /// Name of the installed binary and its symlink in `~/.local/bin`
+    pub binary: &'static str,
+    /// Template for the directory listing the tool's versions
+    pub listing_template: &'static str,
+    /// Template for a version's download
+    pub download_template: &'static str,
+    /// How the download is packaged
+    pub packaging: Packaging,
+    /// Names the binary may have inside a tarball, in order of preference
+    pub archive_members: &'static [&'static str],
+    /// Prefix of version directory names in the listing (e.g. `v` for `v0.22.0`)
+    pub version_dir_prefix: &'static str,
+    /// Other binaries shipped in the tarball, installed next to the tool's
+    /// binary as `<name>-<version>` when present
+    pub bundled: &'static [&'static str],
+}
+
+impl Tool {
+    /// The OpenShift client
+    pub const OC: Tool = Tool {
+        name: "oc",
+        binary: "oc",
+        listing_template: "{mirror_path}/clients/ocp/",
+        download_template: "{mirror_path}/clients/ocp/{version}/openshift-client-{os}-{version}.tar.gz",
+        packaging: Packaging::TarGz,
+        archive_members: &["oc"],
+        version_dir_prefix: "",
+        bundled: &["kubectl"],
+    };
+
+    /// The OpenShift installer
+    pub const OPENSHIFT_INSTALL: Tool = Tool {
+        name: "openshift-install",
+        binary: "openshift-install",
+        listing_template: "{mirror_path}/clients/ocp/",
+        download_template: "{mirror_path}/clients/ocp/{version}/openshift-install-{os}-{version}.tar.gz",
+        packaging: Packaging::TarGz,
+        archive_members: &["openshift-install"],
+        version_dir_prefix: "",
+        bundled: &[],
+    };
+
+    /// The oc plugin for mirroring release and operator content
+    pub const OC_MIRROR: Tool = Tool {
+        name: "oc-mirror",
+        binary: "oc-mirror",
+        listing_template: "{mirror_path}/clients/ocp/",
+        download_template: "{mirror_path}/clients/ocp/{version}/oc-mirror.tar.gz",
+        packaging: Packaging::TarGz,
+        archive_members: &["oc-mirror"],
+        version_dir_prefix: "",
+        bundled: &[],
+    };
+
+    /// The operator package manager
+    pub const OPM: Tool = Tool {
+        name: "opm",
+        binary: "opm",
+        listing_template: "{mirror_path}/clients/ocp/",
+        download_template: "{mirror_path}/clients/ocp/{version}/opm-{os}-{version}.tar.gz",
+        packaging: Packaging::TarGz,
+        // Newer tarballs name the binary after the RHEL release it targets
+        archive_members: &["opm", "opm-rhel9", "opm-rhel8"],
+        version_dir_prefix: "",
+        bundled: &[],
+    };
+
+    /// The Butane config transpiler
+    pub const BUTANE: Tool = Tool {
+        name: "butane",
+        binary: "butane",
+        listing_template: "{mirror_path}/clients/butane/",
+        download_template: "{mirror_path}/clients/butane/v{version}/butane-{arch}",
+        packaging: Packaging::Binary,
+        archive_members: &[],
+        version_dir_prefix: "v",
+        bundled: &[],
+    };
+
+    /// The Helm package manager
+    pub const HELM: Tool = Tool {
+        name: "helm",
+        binary: "helm",
+        listing_template: "{mirror_path}/clients/helm/",
+        download_template: "{mirror_path}/clients/helm/{version}/helm-{os}-{arch}",
+        packaging: Packaging::Binary,
+        archive_members: &[],
+        version_dir_prefix: "",
+        bundled: &[],
+    };
+
+    /// All supported tools
+    pub const ALL: [&'static Tool; 6] = [
+        &Self::OC,
+        &Self::OPENSHIFT_INSTALL,
+        &Self::OC_MIRROR,
+        &Self::OPM,
+        &Self::BUTANE,
+        &Self::HELM,
+    ];
+
+    /// Look up a tool by name
+    ///
+    /// # Examples
+    /// ```
+    /// use ovc::tool::Tool;
+    /// assert_eq!(Tool::find("helm").unwrap().binary, "helm");
+    /// assert!(Tool::find("kubectl").is_none());
+    /// ```
+    #[must_use]
+    pub fn find(name: &str) -> Option<&'static Tool> {
+        Self::ALL.into_iter().find(|tool| tool.name == name)
+    }
+
+    /// Whether this is the OpenShift client itself
+    #[must_use]
+    pub fn is_oc(&self) -> bool {
+        self.name == Self::OC.name
+    }
+
+    /// Directory holding the tool's versioned binaries, relative to `$HOME`
+    ///
+    /// For oc this is `OC_BIN_DIR`.
+    #[must_use]
+    pub fn bin_dir(&self) -> String {
+        format!(".local/bin/{}_bins", self.binary)
+    }
+
+    /// File name of an installed version (e.g. `oc-4.19.0`)
+    #[must_use]
+    pub fn file_name(&self, version: &str) -> String {
+        format!("{}-{version}", self.binary)
+    }
+
+    /// Version of an installed file name, if it belongs to this tool
+    #[must_use]
+    pub fn version_from_file_name<'a>(&self, file_name: &'a str) -> Option<&'a str> {
+        file_name
+            .strip_prefix(self.binary)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .filter(|version| version.chars().next().is_some_and(|c| c.is_ascii_digit()))
+    }
+
+    /// The tool whose downloads the shared version listing cache refers to
+    ///
+    /// Tools published in the same directory share one listing, and the
+    /// cached download URLs are those of the first such tool (oc for
+    /// `clients/ocp/`).
+    #[must_use]
+    pub fn listing_owner(&self) -> &'static Tool {
+        Self::ALL
+            .into_iter()
+            .find(|tool| tool.listing_template == self.listing_template)
+            .unwrap_or(&Self::OC)
+    }
+
+    /// Build the URL listing the tool's versions on a mirror
+    ///
+    /// # Arguments
+    /// * `platform` - Platform to list versions for
+    /// * `mirror_base` - Mirror base URL laid out like `OC_MIRROR_BASE`
+    #[must_use]
+    pub fn build_versions_url_for(&self, platform: &Platform, mirror_base: &str) -> String {
+        format!(
+            "{}/{}",
+            mirror_base.trim_end_matches('/'),
+            expand(self.listing_template, platform, "")
+        )
+    }
+
+    /// Build the download URL of a version on a mirror
+    ///
+    /// # Arguments
+    /// * `platform` - Platform to download for
+    /// * `mirror_base` - Mirror base URL laid out like `OC_MIRROR_BASE`
+    /// * `version` - Version to download (e.g. "4.19.0")
+    #[must_use]
+    pub fn build_download_url_for(
+        &self,
+        platform: &Platform,
+        mirror_base: &str,
+        version: &str,
+    ) -> String {
+        format!(
+            "{}/{}",
+            mirror_base.trim_end_matches('/'),
+            expand(self.download_template, platform, version)
+        )
+    }
+
+    /// Name to store a download under in the archive cache
+    ///
+    /// Usually the download's file name, prefixed with the version when the
+    /// file name doesn't already carry it (e.g. `oc-mirror.tar.gz`).
+    #[must_use]
+    pub fn archive_cache_name(&self, platform: &Platform, version: &str) -> String {
+        let template = expand(self.download_template, platform, version);
+        let file_name = template.rsplit('/').next().unwrap_or(&template);
+        if file_name.contains(version) {
+            file_name.to_string()
+        } else {
+            format!("{}-{version}-{file_name}", self.name)
+        }
+    }
+}
+
+/// Fill in a path template
+fn expand(template: &str, platform: &Platform, version: &str) -> String {
+    template
+        .replace("{mirror_path}", platform.mirror_path)
+        .replace("{os}", platform.binary_suffix)
+        .replace("{arch}", platform.go_arch())
+        .replace("{version}", version)
+}
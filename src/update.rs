@@ -1,13 +1,11 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use std::error::Error;
-use std::fmt::Write as FmtWrite;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use sha2::{Digest, Sha256};
-
+use ovc::checksum::sha256_file;
 use ovc::compare_versions;
 
 const GITHUB_OWNER: &str = "t-c-l-o-u-d";
@@ -34,7 +32,7 @@ fn run_update(verbose: bool) -> Result<(), Box<dyn Error>> {
         eprintln!("ovc: checking for updates (current: v{current})...");
     }
 
-    let (latest, bin_url, sha_url) = get_latest_github_release(verbose)?;
+    let (latest, bin_url, sha_url) = get_github_release(None, verbose)?;
 
     if compare_versions(&latest, current) != std::cmp::Ordering::Greater {
         if verbose {
@@ -43,25 +41,91 @@ fn run_update(verbose: bool) -> Result<(), Box<dyn Error>> {
         return Ok(());
     }
 
+    install_release(&latest, &bin_url, &sha_url, verbose)
+}
+
+/// Check for or install an ovc release on request
+///
+/// Unlike the automatic update, this ignores the cooldown and can install
+/// any release, including an older one. Only an installed release restarts
+/// the cooldown, so `--check` never postpones the automatic update.
+///
+/// # Arguments
+/// * `check` - Only report whether a newer release exists
+/// * `version` - Release to install instead of the latest
+/// * `verbose` - Whether to show progress information
+///
+/// # Errors
+/// Returns error if the release cannot be found, downloaded or installed
+pub fn self_update(
+    check: bool,
+    version: Option<&str>,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
+    let current = env!("CARGO_PKG_VERSION");
+    let (release, bin_url, sha_url) = get_github_release(version, verbose)?;
+    let newer = compare_versions(&release, current) == std::cmp::Ordering::Greater;
+
+    if check {
+        if newer {
+            println!("Update available: v{release} (current: v{current})");
+        } else {
+            println!("Up to date: v{current}");
+        }
+        return Ok(());
+    }
+
+    if release == current || (version.is_none() && !newer) {
+        println!("Already at v{current}");
+        return Ok(());
+    }
+    install_release(&release, &bin_url, &sha_url, verbose)?;
+    record_cooldown();
+    Ok(())
+}
+
+/// Download, verify and swap in a release, announcing the change
+fn install_release(
+    release: &str,
+    bin_url: &str,
+    sha_url: &str,
+    verbose: bool,
+) -> Result<(), Box<dyn Error>> {
     if verbose {
-        eprintln!("ovc: downloading v{latest} from {bin_url}");
+        eprintln!("ovc: downloading v{release} from {bin_url}");
     }
 
     let exe = std::env::current_exe()?;
     let tmp = exe.with_extension("update");
-    download_file(&bin_url, &tmp)?;
-    verify_sha256(&sha_url, &tmp)?;
+    download_file(bin_url, &tmp)?;
+    verify_sha256(sha_url, &tmp)?;
     replace_binary(&tmp, &exe)?;
 
-    if verbose {
-        eprintln!("ovc: updated from v{current} to v{latest}");
-    }
+    // Always shown: the binary changed under the user
+    eprintln!(
+        "ovc: updated from v{} to v{release} ({})",
+        env!("CARGO_PKG_VERSION"),
+        exe.display()
+    );
     Ok(())
 }
 
-fn get_latest_github_release(verbose: bool) -> Result<(String, String, String), Box<dyn Error>> {
-    let api_url =
-        format!("https://api.github.com/repos/{GITHUB_OWNER}/{GITHUB_REPO}/releases/latest");
+/// Fetch a release's version and asset URLs
+///
+/// # Arguments
+/// * `version` - Release version (with or without `v`), or `None` for the
+///   latest release
+fn get_github_release(
+    version: Option<&str>,
+    verbose: bool,
+) -> Result<(String, String, String), Box<dyn Error>> {
+    let release_path = match version {
+        Some(version) => format!("tags/v{}", version.trim_start_matches('v')),
+        None => "latest".to_string(),
+    };
+    let api_url = format!(
+        "https://api.github.com/repos/{GITHUB_OWNER}/{GITHUB_REPO}/releases/{release_path}"
+    );
 
     if verbose {
         eprintln!("Fetching release info from: {api_url}");
@@ -73,6 +137,11 @@ fn get_latest_github_release(verbose: bool) -> Result<(String, String, String),
 
     let resp = client.get(&api_url).send()?;
 
+    if resp.status() == reqwest::StatusCode::NOT_FOUND
+        && let Some(version) = version
+    {
+        return Err(format!("No ovc release v{}", version.trim_start_matches('v')).into());
+    }
     if !resp.status().is_success() {
         return Err(format!(
             "Failed to fetch release info: {} ({})",
@@ -158,13 +227,7 @@ fn verify_sha256(sha_url: &str, bin_path: &Path) -> Result<(), Box<dyn Error>> {
         .ok_or("malformed sha256 file")?
         .to_lowercase();
 
-    let bytes = fs::read(bin_path)?;
-    let actual = Sha256::digest(&bytes)
-        .iter()
-        .fold(String::with_capacity(64), |mut s, b| {
-            write!(s, "{b:02x}").expect("write to String is infallible");
-            s
-        });
+    let actual = sha256_file(bin_path)?;
 
     if actual != expected {
         let _ = fs::remove_file(bin_path);
@@ -257,3 +257,128 @@ pub fn matches_version_pattern(version: &str, pattern: &str) -> bool {
     // Check if version starts with pattern followed by a dot or dash
     version.starts_with(&format!("{pattern}.")) || version.starts_with(&format!("{pattern}-"))
 }
+
+/// How far a client is from the cluster it talks to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Skew {
+    /// Same minor version as the cluster
+    Supported,
+    /// One minor version older or newer; most commands work
+    Warning,
+    /// Further apart, or a different major version
+    Unsupported,
+}
+
+impl std::fmt::Display for Skew {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Supported => "supported",
+            Self::Warning => "warning",
+            Self::Unsupported => "unsupported",
+        })
+    }
+}
+
+/// Major and minor of a version as numbers
+///
+/// A leading `v` and any pre-release or build suffix are ignored, so this
+/// reads OpenShift and Kubernetes versions alike.
+pub(crate) fn major_minor_numbers(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim_start_matches('v').split(['.', '-', '+']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Classify the skew between an oc version and the cluster's version
+///
+/// oc is supported at the cluster's own minor version. One minor apart
+/// still mostly works but misses or mishandles newer APIs, so it is a
+/// warning; anything further is unsupported. Both versions may also be
+/// Kubernetes versions, for clusters that report no OpenShift version.
+///
+/// # Arguments
+/// * `client_version` - oc version (e.g. "4.15.9")
+/// * `server_version` - Cluster version (e.g. "4.16.3")
+///
+/// # Returns
+/// The skew, or `None` if either version lacks a numeric major and minor
+///
+/// # Examples
+/// ```
+/// use ovc::version::{Skew, classify_skew};
+/// assert_eq!(classify_skew("4.16.0", "4.16.3"), Some(Skew::Supported));
+/// assert_eq!(classify_skew("4.17.1", "4.16.3"), Some(Skew::Warning));
+/// assert_eq!(classify_skew("4.12.5", "4.16.3"), Some(Skew::Unsupported));
+/// assert_eq!(classify_skew("1.28", "v1.29.6+aa8e2c4"), Some(Skew::Warning));
+/// assert_eq!(classify_skew("latest", "4.16.3"), None);
+/// ```
+#[must_use]
+pub fn classify_skew(client_version: &str, server_version: &str) -> Option<Skew> {
+    let (client_major, client_minor) = major_minor_numbers(client_version)?;
+    let (server_major, server_minor) = major_minor_numbers(server_version)?;
+
+    Some(if client_major != server_major {
+        Skew::Unsupported
+    } else {
+        match client_minor.abs_diff(server_minor) {
+            0 => Skew::Supported,
+            1 => Skew::Warning,
+            _ => Skew::Unsupported,
+        }
+    })
+}
+
+/// Kubernetes minor each OpenShift 4 minor is based on
+pub const OPENSHIFT_KUBERNETES_MINORS: &[(&str, &str)] = &[
+    ("4.1", "1.13"),
+    ("4.2", "1.14"),
+    ("4.3", "1.16"),
+    ("4.4", "1.17"),
+    ("4.5", "1.18"),
+    ("4.6", "1.19"),
+    ("4.7", "1.20"),
+    ("4.8", "1.21"),
+    ("4.9", "1.22"),
+    ("4.10", "1.23"),
+    ("4.11", "1.24"),
+    ("4.12", "1.25"),
+    ("4.13", "1.26"),
+    ("4.14", "1.27"),
+    ("4.15", "1.28"),
+    ("4.16", "1.29"),
+    ("4.17", "1.30"),
+    ("4.18", "1.31"),
+    ("4.19", "1.32"),
+    ("4.20", "1.33"),
+];
+
+/// Get the OpenShift minor built on a Kubernetes version
+///
+/// Plain Kubernetes clusters don't report an OpenShift version, so this is
+/// how the oc release shipping their Kubernetes minor is found.
+///
+/// # Arguments
+/// * `kubernetes_version` - Kubernetes version as reported by `/version`
+///   (e.g. "v1.30.2" or "1.29.6+aa8e2c4")
+///
+/// # Returns
+/// OpenShift major.minor, or `None` if no OpenShift minor ships it
+///
+/// # Examples
+/// ```
+/// use ovc::version::openshift_minor;
+/// assert_eq!(openshift_minor("v1.30.2"), Some("4.17"));
+/// assert_eq!(openshift_minor("1.29.6+aa8e2c4"), Some("4.16"));
+/// assert_eq!(openshift_minor("1.31.1-eks-a737599"), Some("4.18"));
+/// assert_eq!(openshift_minor("1.15.3"), None);
+/// ```
+#[must_use]
+pub fn openshift_minor(kubernetes_version: &str) -> Option<&'static str> {
+    let (major, minor) = major_minor_numbers(kubernetes_version)?;
+    let minor = format!("{major}.{minor}");
+    OPENSHIFT_KUBERNETES_MINORS
+        .iter()
+        .find(|(_, kubernetes)| *kubernetes == minor)
+        .map(|&(openshift, _)| openshift)
+}